@@ -0,0 +1,50 @@
+//! Criterion benchmarks of parsing and both parts of every day, on the inputs in `inputs/`.
+//!
+//! Run with `cargo bench`, or `cargo bench -- day16` for a single day.
+
+use std::hint::black_box;
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc_22_rust::days::*;
+use aoc_22_rust::input::Source;
+use aoc_22_rust::solution::Solution;
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let source = Source::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"));
+    let raw_input = source.load(S::DAY).unwrap();
+    let input = S::parse(&raw_input).unwrap();
+
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+    // a few days take most of a second per part
+    group.sample_size(10);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&raw_input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&input))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&input))));
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_day::<day01::Day01>,
+    bench_day::<day02::Day02>,
+    bench_day::<day03::Day03>,
+    bench_day::<day04::Day04>,
+    bench_day::<day05::Day05>,
+    bench_day::<day06::Day06>,
+    bench_day::<day07::Day07>,
+    bench_day::<day08::Day08>,
+    bench_day::<day09::Day09>,
+    bench_day::<day10::Day10>,
+    bench_day::<day11::Day11>,
+    bench_day::<day12::Day12>,
+    bench_day::<day13::Day13>,
+    bench_day::<day14::Day14>,
+    bench_day::<day15::Day15>,
+    bench_day::<day16::Day16>,
+    bench_day::<day17::Day17>,
+    bench_day::<day18::Day18>,
+    bench_day::<day19::Day19>,
+);
+criterion_main!(benches);
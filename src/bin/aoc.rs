@@ -0,0 +1,116 @@
+use std::path::Path;
+use std::{env, process};
+
+use aoc_22_rust::cli::{Command, Days, USAGE};
+use aoc_22_rust::days;
+use aoc_22_rust::error::Error;
+use aoc_22_rust::fetch::{self, Client};
+use aoc_22_rust::input::Source;
+use aoc_22_rust::solution::{Part, Runner};
+use aoc_22_rust::timing::{self, Format, Row};
+
+// prints the answer of each part solved and the error of each part that failed, and returns the
+// timings with whether every part was solved
+fn solve(runner: &dyn Runner, input: &Source, part: Part, time: Option<Format>) -> Result<(Row, bool), String> {
+    let day = runner.day();
+    let answers = input
+        .load(day)
+        .map_err(Error::from)
+        .and_then(|input| runner.run(&input, part))
+        .map_err(|err| format!("day {day}: {err}"))?;
+    // the markdown and json reports replace the answers, but not the errors
+    let text = matches!(time, None | Some(Format::Text));
+    if text {
+        println!("Day {day:02}");
+    }
+    let mut solved = true;
+    for (n, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+        match answer {
+            Some(Ok(answer)) if text => println!("Part {n}: {answer}"),
+            Some(Err(err)) => {
+                eprintln!("error: day {day} part {n}: {err}");
+                solved = false;
+            }
+            _ => {}
+        }
+    }
+    if text && time.is_some() {
+        println!("Time: {}", timing::text(&answers.timings));
+    }
+    Ok((Row { day, timings: answers.timings }, solved))
+}
+
+fn fetch(days: Days, dir: &Path) -> Result<(), String> {
+    let mut client = Client::new(fetch::session_token().map_err(|err| err.to_string())?);
+    let days = match days {
+        Days::One(day) => vec![day],
+        Days::All => days::all().map(|runner| runner.day()).collect(),
+    };
+    for day in days {
+        let downloads = fetch::download(&mut client, dir, day).map_err(|err| format!("day {day}: {err}"))?;
+        for download in downloads {
+            println!("{download}");
+        }
+    }
+    Ok(())
+}
+
+fn run(command: Command) -> Result<(), String> {
+    let (days, part, input, time) = match command {
+        Command::Solve { days, part, input, time } => (days, part, input, time),
+        Command::Fetch { days, dir } => return fetch(days, &dir),
+        Command::Help => {
+            println!("{USAGE}");
+            return Ok(());
+        }
+    };
+    let runners = match days {
+        Days::One(day) => vec![days::get(day).ok_or_else(|| format!("day {day} is not solved yet"))?],
+        Days::All => {
+            if !matches!(input, Source::Dir(_)) {
+                return Err(format!("--all needs an inputs directory, {input} is not one"));
+            }
+            days::all().collect()
+        }
+    };
+    // a day that fails does not stop the others
+    let mut failed = false;
+    let mut rows = vec![];
+    for runner in runners {
+        match solve(runner, &input, part, time) {
+            Ok((row, solved)) => {
+                rows.push(row);
+                failed |= !solved;
+            }
+            Err(message) => {
+                eprintln!("error: {message}");
+                failed = true;
+            }
+        }
+    }
+    match time {
+        Some(Format::Markdown) => print!("{}", timing::markdown(&rows)),
+        Some(Format::Json) => print!("{}", timing::json(&rows)),
+        Some(Format::Text) if rows.len() > 1 => {
+            let total = rows.iter().map(|row| row.timings.total()).sum();
+            println!("Total time: {}", timing::format_duration(total));
+        }
+        _ => {}
+    }
+    if failed {
+        // the errors are already printed
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn main() {
+    let command = Command::parse(env::args().skip(1)).unwrap_or_else(|message| {
+        eprintln!("error: {message}\n\n{USAGE}");
+        process::exit(2);
+    });
+    if let Err(message) = run(command) {
+        eprintln!("error: {message}");
+        process::exit(1);
+    }
+}
@@ -1,20 +1,12 @@
-use std::fs;
+use std::env;
 
+use aoc_22_rust::days::day01::Day01;
+use aoc_22_rust::input;
+use aoc_22_rust::solution::Solution;
 
 fn main() {
-    let mut calories: Vec<u32> = fs::read_to_string("inputs/day01.in")
-        .expect("Failed to read input")
-        .split("\n\n")
-        .map(|s| {
-            s.lines()
-            .map(|i| i.parse::<u32>().unwrap())
-            .sum()
-        })
-        .collect();
-
-    calories.sort();
-    calories.reverse();
-
-    println!("{}", calories[0]);
-    println!("{}", calories[0] + calories[1] + calories[2]);
-}
\ No newline at end of file
+    // optional input file or directory, `-` for stdin
+    let input = input::load_or_exit::<Day01>(env::args().nth(1));
+    println!("Part 1: {}", input::or_exit(Day01::part1(&input)));
+    println!("Part 2: {}", input::or_exit(Day01::part2(&input)));
+}
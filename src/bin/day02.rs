@@ -1,23 +1,12 @@
-use std::fs;
+use std::env;
 
-fn main() {
-    let input: String = fs::read_to_string("inputs/day02.in").expect("Failed to read input");
-
-    let rounds: Vec<&str> = input.split("\n").collect();
-
-    let combos_p1 = ["B X", "C Y", "A Z", "A X", "B Y", "C Z", "C X", "A Y", "B Z"];
-    let combos_p2 = ["B X", "C X", "A X", "A Y", "B Y", "C Y", "C Z", "A Z", "B Z"];
+use aoc_22_rust::days::day02::Day02;
+use aoc_22_rust::input;
+use aoc_22_rust::solution::Solution;
 
-    let points_pt1: usize = rounds
-        .iter()
-        .map(|round| combos_p1.iter().position(|&r| r == *round).unwrap() + 1)
-        .sum();
-    
-    let points_pt2: usize = rounds
-        .iter()
-        .map(|round| combos_p2.iter().position(|&r| r == *round).unwrap() + 1)
-        .sum();
-
-    println!("{}", points_pt1);
-    println!("{}", points_pt2);
-}
\ No newline at end of file
+fn main() {
+    // optional input file or directory, `-` for stdin
+    let input = input::load_or_exit::<Day02>(env::args().nth(1));
+    println!("Part 1: {}", input::or_exit(Day02::part1(&input)));
+    println!("Part 2: {}", input::or_exit(Day02::part2(&input)));
+}
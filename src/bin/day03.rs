@@ -1,37 +1,12 @@
+use std::env;
 
-fn priority(b: &u8) -> u32 {
-    if *b >= b'a' {
-        (b - b'a') as u32 + 1
-    } else {
-        (b - b'A') as u32 + 27
-    }
-}
+use aoc_22_rust::days::day03::Day03;
+use aoc_22_rust::input;
+use aoc_22_rust::solution::Solution;
 
 fn main() {
-    let task_input = include_bytes!("../../inputs/day03.in");
-    let pt1 = task_input
-        .split(|b| *b == b'\n')
-        .map(|l| l.split_at(l.len() / 2))
-        .map(|(a, b)| 
-            b.iter()
-            .filter(|b| a.contains(b))
-            .map(|b| priority(b))
-            .next()
-            .unwrap()
-        )
-        .sum::<u32>();
-    
-    let pt2 = task_input
-        .split(|b| *b == b'\n')
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .map(|slice| slice[0].iter()
-                .find(|b| slice[1].contains(b) && slice[2].contains(b))
-                .unwrap()
-        )
-        .map(|b| priority(b))
-        .sum::<u32>();
-
-    println!("{}", pt1);
-    println!("{}", pt2);
-}       
\ No newline at end of file
+    // optional input file or directory, `-` for stdin
+    let input = input::load_or_exit::<Day03>(env::args().nth(1));
+    println!("Part 1: {}", input::or_exit(Day03::part1(&input)));
+    println!("Part 2: {}", input::or_exit(Day03::part2(&input)));
+}
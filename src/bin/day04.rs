@@ -1,50 +1,12 @@
-use std::fs;
+use std::env;
 
+use aoc_22_rust::days::day04::Day04;
+use aoc_22_rust::input;
+use aoc_22_rust::solution::Solution;
 
 fn main() {
-    let task_input = fs::read_to_string("inputs/day04.in").expect("fars");
-
-    let pt1 = task_input
-        .lines()
-        .map(|l| {
-            let (first, second) = l.split_once(',').unwrap();
-            let ((a, b), (c, d)) = (first.split_once('-').unwrap(), second.split_once('-').unwrap());
-            (
-                a.parse::<u32>().unwrap(),
-                b.parse::<u32>().unwrap(),
-                c.parse::<u32>().unwrap(),
-                d.parse::<u32>().unwrap()
-            )})
-        .filter(|(a, b, c, d)| {
-            (a >= c && b <= d) || (c >= a && d <= b)
-        })
-        .count();
-    
-    let pt2 = task_input
-        .lines()
-        .map(|l| {
-            let (first, second) = l.split_once(',').unwrap();
-            let ((a, b), (c, d)) = (first.split_once('-').unwrap(), second.split_once('-').unwrap());
-            (
-                a.parse::<u32>().unwrap(),
-                b.parse::<u32>().unwrap(),
-                c.parse::<u32>().unwrap(),
-                d.parse::<u32>().unwrap()
-            )})
-        .filter_map(|(a, b, c, d)| {
-            let first = a..b + 1;
-            let second = c..d + 1;
-            
-            for i in first {
-                if second.contains(&i) {
-                    return Some(true);
-                } 
-            }
-            None
-        })
-        .count();
-
-    println!("{}", pt1);
-    println!("{}", pt2);
-        
-}
\ No newline at end of file
+    // optional input file or directory, `-` for stdin
+    let input = input::load_or_exit::<Day04>(env::args().nth(1));
+    println!("Part 1: {}", input::or_exit(Day04::part1(&input)));
+    println!("Part 2: {}", input::or_exit(Day04::part2(&input)));
+}
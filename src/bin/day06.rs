@@ -1,23 +1,12 @@
-use itertools::Itertools;
-
-
-fn start_of_marker(stream: &[u8], length: usize) -> usize {
-    length + stream
-        .windows(length)
-        .position(|window| window
-            .iter()
-            .tuple_combinations::<(&u8, &u8)>()
-            .all(|(a, b)| a != b))
-            .unwrap()
-}
+use std::env;
 
+use aoc_22_rust::days::day06::Day06;
+use aoc_22_rust::input;
+use aoc_22_rust::solution::Solution;
 
 fn main() {
-    let raw_input = include_bytes!("../../inputs/day06.in");
-
-    let start_of_packet_marker = start_of_marker(raw_input, 4);
-
-    let start_of_message_marker = start_of_marker(raw_input, 14);
-
-    println!("PT1: {}\nPT2: {}", start_of_packet_marker, start_of_message_marker);
-}
\ No newline at end of file
+    // optional input file or directory, `-` for stdin
+    let input = input::load_or_exit::<Day06>(env::args().nth(1));
+    println!("Part 1: {}", input::or_exit(Day06::part1(&input)));
+    println!("Part 2: {}", input::or_exit(Day06::part2(&input)));
+}
@@ -0,0 +1,188 @@
+//! Command line arguments of the `aoc` runner binary.
+
+use std::path::PathBuf;
+
+use crate::input::Source;
+use crate::solution::Part;
+use crate::timing::Format;
+
+pub const USAGE: &str = "\
+Usage: aoc --day <N> [--part <1|2>] [--input <PATH>] [--time] [--format <FORMAT>]
+       aoc --all [--part <1|2>] [--input <DIR>] [--time] [--format <FORMAT>]
+       aoc fetch (--day <N> | --all) [--input <DIR>]
+
+Commands:
+  fetch               Download puzzle inputs and examples into DIR (default: $AOC_INPUTS
+                      or inputs/), skipping files already there. Needs a session token
+                      in $AOC_SESSION or ~/.config/aoc/session
+
+Options:
+  -d, --day <N>       Solve a single day
+  -a, --all           Solve every day
+  -p, --part <1|2>    Solve only one part (default: both)
+  -i, --input <PATH>  Read the puzzle input from a file, from DIR/dayNN.in when PATH
+                      is a directory, or from stdin when PATH is `-`
+                      (default: stdin with --day, $AOC_INPUTS or inputs/ with --all)
+  -t, --time          Report how long parsing and each part took
+  -f, --format <FORMAT>
+                      Timing report format, implies --time: `text` after each day's
+                      answers (default), or a `markdown` table or `json` in place of them
+  -h, --help          Print this help";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Days {
+    One(u8),
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Solve {
+        days: Days,
+        part: Part,
+        input: Source,
+        /// Timing report to print, if any.
+        time: Option<Format>,
+    },
+    Fetch {
+        days: Days,
+        dir: PathBuf,
+    },
+    Help,
+}
+
+impl Command {
+    /// Parses the arguments that follow the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+        let mut days = None;
+        let mut part = Part::Both;
+        let mut input = None;
+        let mut time = None;
+
+        let mut args = args.into_iter().peekable();
+        let fetch = args.next_if(|arg| arg == "fetch").is_some();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Command::Help),
+                "-a" | "--all" => days = Some(Days::All),
+                "-d" | "--day" => {
+                    let value = value_of(&arg, args.next())?;
+                    let day = value
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|day| (1..=25).contains(day))
+                        .ok_or_else(|| format!("invalid day `{value}`, expected 1-25"))?;
+                    days = Some(Days::One(day));
+                }
+                "-p" | "--part" => {
+                    part = match value_of(&arg, args.next())?.as_str() {
+                        "1" => Part::One,
+                        "2" => Part::Two,
+                        value => return Err(format!("invalid part `{value}`, expected 1 or 2")),
+                    }
+                }
+                "-i" | "--input" => {
+                    let value = value_of(&arg, args.next())?;
+                    input = Some(Source::from_arg(&value));
+                }
+                "-t" | "--time" => time = time.or(Some(Format::Text)),
+                "-f" | "--format" => {
+                    time = Some(match value_of(&arg, args.next())?.as_str() {
+                        "text" => Format::Text,
+                        "markdown" => Format::Markdown,
+                        "json" => Format::Json,
+                        value => return Err(format!("invalid format `{value}`, expected text, markdown or json")),
+                    })
+                }
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+
+        let days = days.ok_or("either --day or --all is required")?;
+        if fetch {
+            if part != Part::Both || time.is_some() {
+                return Err("fetch only takes --day, --all and --input".to_string());
+            }
+            return match input.unwrap_or_else(Source::default_dir) {
+                Source::Dir(dir) => Ok(Command::Fetch { days, dir }),
+                // not created yet, so it was read as a file
+                Source::File(path) => Ok(Command::Fetch { days, dir: path }),
+                Source::Stdin => Err("fetch needs an inputs directory, not stdin".to_string()),
+            };
+        }
+        let input = match (&days, input) {
+            (Days::All, Some(Source::Stdin)) => {
+                return Err("--all needs an inputs directory, not stdin".to_string())
+            }
+            (_, Some(input)) => input,
+            (Days::One(_), None) => Source::Stdin,
+            (Days::All, None) => Source::default_dir(),
+        };
+        Ok(Command::Solve { days, part, input, time })
+    }
+}
+
+fn value_of(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("missing value for `{flag}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        Command::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn single_day_defaults_to_stdin() {
+        let command = parse("--day 7 --part 2").unwrap();
+        assert_eq!(
+            command,
+            Command::Solve { days: Days::One(7), part: Part::Two, input: Source::Stdin, time: None }
+        );
+    }
+
+    #[test]
+    fn timing_format() {
+        let time = |args| match parse(args).unwrap() {
+            Command::Solve { time, .. } => time,
+            _ => panic!("expected a solve command"),
+        };
+        assert_eq!(time("--all --time"), Some(Format::Text));
+        assert_eq!(time("--all --format json --time"), Some(Format::Json));
+        assert_eq!(time("--all -f markdown"), Some(Format::Markdown));
+        assert!(parse("--all --format yaml").is_err());
+    }
+
+    #[test]
+    fn input_file() {
+        let command = parse("-d 3 -i some/file.in").unwrap();
+        let Command::Solve { input, .. } = command else { panic!("expected a solve command") };
+        assert_eq!(input, Source::File("some/file.in".into()));
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(parse("").is_err());
+        assert!(parse("--day 26").is_err());
+        assert!(parse("--day 3 --part 3").is_err());
+        assert!(parse("--day").is_err());
+        assert!(parse("--all --input -").is_err());
+        assert!(parse("--frobnicate").is_err());
+    }
+
+    #[test]
+    fn fetch() {
+        assert_eq!(parse("fetch -d 5 -i new/dir"), Ok(Command::Fetch { days: Days::One(5), dir: "new/dir".into() }));
+        assert!(matches!(parse("fetch --all"), Ok(Command::Fetch { days: Days::All, .. })));
+        assert!(parse("fetch --day 5 --part 1").is_err());
+        assert!(parse("fetch --day 5 --input -").is_err());
+        assert!(parse("--day 5 fetch").is_err());
+    }
+
+    #[test]
+    fn help() {
+        assert_eq!(parse("--day 3 --help"), Ok(Command::Help));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::error::Result;
+use crate::parse;
+use crate::solution::Solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    // calories carried by each elf, most first
+    type Input = Vec<u32>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut calories: Vec<u32> = parse::blocks(input)
+            .map(|s| {
+                s.lines()
+                .map(|i| parse::number::<u32>(input, i))
+                .sum::<Result<u32>>()
+            })
+            .collect::<Result<_>>()?;
+
+        calories.sort();
+        calories.reverse();
+        Ok(calories)
+    }
+
+    fn part1(calories: &Self::Input) -> Result<u32> {
+        Ok(calories[0])
+    }
+
+    fn part2(calories: &Self::Input) -> Result<u32> {
+        // every elf when there are fewer than three
+        Ok(calories.iter().take(3).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    #[test]
+    fn example() {
+        let calories = Day01::parse(EXAMPLE).unwrap();
+        assert_eq!(Day01::part1(&calories).unwrap(), 24000);
+        assert_eq!(Day01::part2(&calories).unwrap(), 45000);
+    }
+
+    #[test]
+    fn fewer_than_three_elves() {
+        let calories = Day01::parse("1000\n2000\n\n4000\n").unwrap();
+        assert_eq!(Day01::part1(&calories).unwrap(), 4000);
+        assert_eq!(Day01::part2(&calories).unwrap(), 7000);
+    }
+
+    #[test]
+    fn reports_invalid_calories() {
+        let err = Day01::parse("1000\n\n20x0\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 1: invalid number `20x0`");
+    }
+}
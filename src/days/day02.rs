@@ -0,0 +1,60 @@
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+const COMBOS_P1: [&str; 9] = ["B X", "C Y", "A Z", "A X", "B Y", "C Z", "C X", "A Y", "B Z"];
+const COMBOS_P2: [&str; 9] = ["B X", "C X", "A X", "A Y", "B Y", "C Y", "C Z", "A Z", "B Z"];
+
+fn total_points(rounds: &[String], combos: &[&str]) -> usize {
+    rounds
+        .iter()
+        .map(|round| combos.iter().position(|&r| r == round).unwrap() + 1)
+        .sum()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|round| {
+                if COMBOS_P1.contains(&round) {
+                    Ok(round.to_string())
+                } else {
+                    Err(Error::at(input, round, format!("expected a round like `A Y`, found `{round}`")))
+                }
+            })
+            .collect()
+    }
+
+    fn part1(rounds: &Self::Input) -> Result<usize> {
+        Ok(total_points(rounds, &COMBOS_P1))
+    }
+
+    fn part2(rounds: &Self::Input) -> Result<usize> {
+        Ok(total_points(rounds, &COMBOS_P2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let rounds = Day02::parse("A Y\nB X\nC Z\n").unwrap();
+        assert_eq!(Day02::part1(&rounds).unwrap(), 15);
+        assert_eq!(Day02::part2(&rounds).unwrap(), 12);
+    }
+
+    #[test]
+    fn rejects_unknown_shapes() {
+        assert!(Day02::parse("A Y\nD X\n").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+fn priority(b: &u8) -> u32 {
+    if *b >= b'a' {
+        (b - b'a') as u32 + 1
+    } else {
+        (b - b'A') as u32 + 27
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    // one rucksack per line
+    type Input = Vec<Vec<u8>>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|l| {
+                if let Some(i) = l.find(|c: char| !c.is_ascii_alphabetic()) {
+                    return Err(Error::at(input, &l[i..], "expected only item letters a-z and A-Z"));
+                }
+                Ok(l.as_bytes().to_vec())
+            })
+            .collect()
+    }
+
+    fn part1(rucksacks: &Self::Input) -> Result<u32> {
+        rucksacks
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                let (a, b) = l.split_at(l.len() / 2);
+                b.iter()
+                    .find(|b| a.contains(b))
+                    .map(priority)
+                    .ok_or_else(|| Error::Invalid(format!("rucksack {} has no item in both compartments", i + 1)))
+            })
+            .sum::<Result<u32>>()
+    }
+
+    fn part2(rucksacks: &Self::Input) -> Result<u32> {
+        if rucksacks.len() % 3 != 0 {
+            return Err(Error::Invalid(format!("{} rucksacks do not split into groups of three", rucksacks.len())));
+        }
+        rucksacks
+            .chunks(3)
+            .enumerate()
+            .map(|(i, slice)| slice[0].iter()
+                    .find(|b| slice[1].contains(b) && slice[2].contains(b))
+                    .map(priority)
+                    .ok_or_else(|| Error::Invalid(format!("group {} has no badge item in common", i + 1)))
+            )
+            .sum::<Result<u32>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn example() {
+        let rucksacks = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part1(&rucksacks).unwrap(), 157);
+        assert_eq!(Day03::part2(&rucksacks).unwrap(), 70);
+    }
+
+    #[test]
+    fn reports_rucksacks_without_a_shared_item() {
+        let rucksacks = Day03::parse("abcd
+").unwrap();
+        assert_eq!(Day03::part1(&rucksacks).unwrap_err().to_string(), "rucksack 1 has no item in both compartments");
+        let rucksacks = Day03::parse(&EXAMPLE.lines().take(4).collect::<Vec<_>>().join("\n")).unwrap();
+        assert!(matches!(Day03::part2(&rucksacks), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn priorities() {
+        assert_eq!(priority(&b'a'), 1);
+        assert_eq!(priority(&b'z'), 26);
+        assert_eq!(priority(&b'A'), 27);
+        assert_eq!(priority(&b'Z'), 52);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::error::Result;
+use crate::geom::Range;
+use crate::parse;
+use crate::solution::Solution;
+
+fn parse_line(input: &str, l: &str) -> Result<(Range, Range)> {
+    let (first, second) = parse::split_once(input, l, ",")?;
+    let ((a, b), (c, d)) = (parse::split_once(input, first, "-")?, parse::split_once(input, second, "-")?);
+    Ok((
+        Range::new(parse::number(input, a)?, parse::number(input, b)?),
+        Range::new(parse::number(input, c)?, parse::number(input, d)?),
+    ))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<(Range, Range)>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(|l| parse_line(input, l)).collect()
+    }
+
+    fn part1(assignments: &Self::Input) -> Result<usize> {
+        Ok(assignments
+            .iter()
+            .filter(|(first, second)| first.contains_range(second) || second.contains_range(first))
+            .count())
+    }
+
+    fn part2(assignments: &Self::Input) -> Result<usize> {
+        Ok(assignments
+            .iter()
+            .filter(|(first, second)| first.overlaps(second))
+            .count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+
+    #[test]
+    fn example() {
+        let assignments = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part1(&assignments).unwrap(), 2);
+        assert_eq!(Day04::part2(&assignments).unwrap(), 4);
+    }
+
+    #[test]
+    fn reports_missing_separator() {
+        let err = Day04::parse("2-4,6-8\n2-3;4-5\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: expected `,` in `2-3;4-5`");
+    }
+}
@@ -0,0 +1,139 @@
+use itertools::Itertools;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub struct Crates {
+    stacks: Vec<Vec<char>>,
+    // (this_many, from, to), stacks numbered from 1
+    moves: Vec<(usize, usize, usize)>,
+}
+
+fn top_of_stacks(stacks: &[Vec<char>]) -> String {
+    // stacks left empty have nothing on top
+    stacks.iter()
+        .filter_map(|stack| stack.last())
+        .join("")
+}
+
+// move `number`, counted from 1, takes more crates than stack `from` holds
+fn too_few_crates(number: usize, from: usize) -> Error {
+    Error::Invalid(format!("move {number} takes more crates than stack {from} holds"))
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Crates;
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(raw_input: &str) -> Result<Self::Input> {
+        let (boxes, moves) = parse::split_once(raw_input, raw_input, "\n\n")?;
+
+        let num_stacks = boxes.lines()
+            .next_back()
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| c.is_numeric())
+            .count();
+
+        let mut stacks: Vec<Vec<char>>  = vec![vec![]; num_stacks];
+
+        boxes.lines()
+            .rev()
+            .skip(1)
+            .map(str::as_bytes)
+            .for_each(|l| {
+                for (i, stack) in stacks.iter_mut().enumerate() {
+                    let val = l.get(1 + i * 4).copied().unwrap_or(b' ');
+                    if val.is_ascii_alphabetic() {
+                        stack.push(val as char);
+                    }
+                }
+            });
+
+        let moves: Vec<(usize, usize, usize)> = moves.lines()
+            .map(|l| {
+                parse::numbers::<usize>(l)
+                    .collect_tuple()
+                    .filter(|&(_, from, to)| (1..=num_stacks).contains(&from) && (1..=num_stacks).contains(&to))
+                    .ok_or_else(|| Error::at(raw_input, l, format!("expected `move N from A to B` with stacks 1-{num_stacks}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Crates { stacks, moves })
+    }
+
+    fn part1(crates: &Self::Input) -> Result<String> {
+        let mut stacks = crates.stacks.clone();
+
+        for (i, &(this_many, from, to)) in crates.moves.iter().enumerate() {
+            for _ in 0..this_many {
+                let move_this = stacks[from - 1].pop().ok_or_else(|| too_few_crates(i + 1, from))?;
+                stacks[to - 1].push(move_this);
+            }
+        }
+
+        Ok(top_of_stacks(&stacks))
+    }
+
+    fn part2(crates: &Self::Input) -> Result<String> {
+        let mut stacks = crates.stacks.clone();
+
+        for (i, &(this_many, from, to)) in crates.moves.iter().enumerate() {
+            let mut temp = vec![];
+            for _ in 0..this_many {
+                let move_this = stacks[from - 1].pop().ok_or_else(|| too_few_crates(i + 1, from))?;
+                temp.push(move_this);
+            }
+            while let Some(item) = temp.pop() {
+                stacks[to - 1].push(item);
+            }
+        }
+
+        Ok(top_of_stacks(&stacks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    #[test]
+    fn example() {
+        let crates = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part1(&crates).unwrap(), "CMZ");
+        assert_eq!(Day05::part2(&crates).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn reports_moves_off_empty_stacks() {
+        let input = EXAMPLE.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let crates = Day05::parse(&input).unwrap();
+        assert_eq!(Day05::part1(&crates).unwrap_err().to_string(), "move 2 takes more crates than stack 1 holds");
+        assert!(Day05::part2(&crates).is_err());
+    }
+
+    #[test]
+    fn rejects_moves_to_missing_stacks() {
+        let input = EXAMPLE.replace("move 1 from 1 to 2", "move 1 from 1 to 4");
+        assert!(Day05::parse(&input).is_err());
+    }
+}
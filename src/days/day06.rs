@@ -0,0 +1,86 @@
+use itertools::Itertools;
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+// characters processed once the first `length` different ones in a row have come in
+fn start_of_marker(stream: &[u8], length: usize) -> Option<usize> {
+    stream
+        .windows(length)
+        .position(|window| window
+            .iter()
+            .tuple_combinations::<(&u8, &u8)>()
+            .all(|(a, b)| a != b))
+        .map(|position| length + position)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<u8>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let stream = input.trim_end().as_bytes().to_vec();
+        if start_of_marker(&stream, 4).is_none() {
+            return Err(Error::Invalid("the stream has no start-of-packet marker".to_string()));
+        }
+        Ok(stream)
+    }
+
+    fn part1(stream: &Self::Input) -> Result<usize> {
+        // start of packet marker, checked for when parsing
+        Ok(start_of_marker(stream, 4).unwrap())
+    }
+
+    fn part2(stream: &Self::Input) -> Result<usize> {
+        // start of message marker
+        start_of_marker(stream, 14)
+            .ok_or_else(|| Error::Invalid("the stream has no start-of-message marker".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Part;
+
+    #[test]
+    fn examples() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (stream, packet, message) in examples {
+            let stream = Day06::parse(stream).unwrap();
+            assert_eq!(Day06::part1(&stream).unwrap(), packet);
+            assert_eq!(Day06::part2(&stream).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn rejects_streams_without_markers() {
+        let err = Day06::parse("aaabaaabaaab\n").unwrap_err();
+        assert_eq!(err.to_string(), "the stream has no start-of-packet marker");
+        assert!(Day06::parse("").is_err());
+    }
+
+    #[test]
+    fn part1_without_a_start_of_message_marker() {
+        let stream = Day06::parse("abcdabcdabcdabcdabcdabcd\n").unwrap();
+        assert_eq!(Day06::part1(&stream).unwrap(), 4);
+        let err = Day06::part2(&stream).unwrap_err();
+        assert_eq!(err.to_string(), "the stream has no start-of-message marker");
+
+        // the runner keeps part 1's answer
+        let answers = crate::days::get(6).unwrap().run("abcdabcdabcdabcdabcdabcd\n", Part::Both).unwrap();
+        assert_eq!(answers.part1, Some(Ok("4".to_string())));
+        assert_eq!(answers.part2, Some(Err("the stream has no start-of-message marker".to_string())));
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+fn calc_dir_size(file_tree: &HashMap<PathBuf, Vec<(u32, &str)>>, dir_sizes: &mut HashMap<PathBuf, u32>, dir_name: &PathBuf) -> Result<u32> {
+    let contents = file_tree.get(dir_name)
+        .ok_or_else(|| Error::Invalid(format!("directory `{}` is never listed", dir_name.display())))?;
+    let size = contents.iter()
+        .map(|&(file_size, file_name)| {
+            match file_size {
+                // size 0 means directory, calculate size of that dir recursively
+                0 => calc_dir_size(file_tree, dir_sizes, &dir_name.join(file_name)),
+                // matches other cases, return file_size
+                s => Ok(s)
+            }
+        })
+        .sum::<Result<u32>>()?;
+    dir_sizes.insert(dir_name.clone(), size);
+    Ok(size)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    // total size of every directory
+    type Input = HashMap<PathBuf, u32>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(task_input: &str) -> Result<Self::Input> {
+        let mut cwd = PathBuf::new();
+        let mut file_tree: HashMap<PathBuf, Vec<(u32, &str)>> = HashMap::new();
+
+        if let Some(junk) = task_input.split('$').next().filter(|s| !s.trim().is_empty()) {
+            return Err(Error::at(task_input, junk, "expected a `$` command"));
+        }
+
+        for chunk in task_input.split('$').skip(1) {
+            let command = chunk.lines().next().unwrap_or_default().trim();
+            match command {
+                "cd .." => {
+                    cwd.pop();
+                },
+                "ls" => { 
+                    let dir_contents = chunk.lines().skip(1)
+                        .map(|l| {
+                            let (size, file_name) = parse::split_once(task_input, l, " ")?;
+                            // size 0 marks a directory
+                            let size = if size == "dir" { 0 } else { parse::number::<u32>(task_input, size)? };
+                            if size == 0 && (file_name.contains('/') || file_name == "." || file_name == "..") {
+                                return Err(Error::at(task_input, file_name, format!("invalid directory name `{file_name}`")));
+                            }
+                            Ok((size, file_name))
+                        })
+                        .collect::<Result<Vec<(u32, &str)>>>()?;
+                    file_tree.insert(cwd.clone(), dir_contents);
+                },
+                _ => match command.strip_prefix("cd ") {
+                    Some(dir_name) => cwd.push(dir_name),
+                    None => return Err(Error::at(task_input, command, format!("unknown command `{command}`"))),
+                }
+            }
+        }
+
+        if !file_tree.contains_key(&PathBuf::from("/")) {
+            return Err(Error::Invalid("the root directory `/` is never listed".to_string()));
+        }
+
+        let mut dir_sizes: HashMap<PathBuf, u32> = HashMap::new();
+
+        for key in file_tree.keys() {
+            calc_dir_size(&file_tree, &mut dir_sizes, key)?;
+        }
+        Ok(dir_sizes)
+    }
+
+    fn part1(dir_sizes: &Self::Input) -> Result<u32> {
+        Ok(dir_sizes.values().filter(|&&size| size <= 100000).sum::<u32>())
+    }
+
+    fn part2(dir_sizes: &Self::Input) -> Result<u32> {
+        let used = dir_sizes[&PathBuf::from("/")];
+        let space_left = 70000000u32.checked_sub(used)
+            .ok_or_else(|| Error::Invalid(format!("the files take up {used}, more than the disk's 70000000")))?;
+        // the root directory itself always frees enough
+        Ok(*dir_sizes.values().filter(|&&size| (space_left  + size) >= 30000000).min().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn example() {
+        let dir_sizes = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(dir_sizes[&PathBuf::from("/a/e")], 584);
+        assert_eq!(dir_sizes[&PathBuf::from("/")], 48381165);
+        assert_eq!(Day07::part1(&dir_sizes).unwrap(), 95437);
+        assert_eq!(Day07::part2(&dir_sizes).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn reports_unknown_commands() {
+        let err = Day07::parse("$ cd /\n$ rm -rf a\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: unknown command `rm -rf a`");
+    }
+
+    #[test]
+    fn reports_unlisted_directories() {
+        let err = Day07::parse("$ cd /\n$ ls\ndir a\n5 b\n").unwrap_err();
+        assert_eq!(err.to_string(), "directory `/a` is never listed");
+    }
+
+    #[test]
+    fn reports_full_disks() {
+        let dir_sizes = Day07::parse("$ cd /\n$ ls\n80000000 big\n").unwrap();
+        assert_eq!(Day07::part1(&dir_sizes).unwrap(), 0);
+        assert!(matches!(Day07::part2(&dir_sizes), Err(Error::Invalid(_))));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::error::Result;
+use crate::grid::{Grid, Pos};
+use crate::solution::Solution;
+
+// (reaches the edge, number of trees seen) looking from a tree of height `tree` along `line`
+fn look<'a>(tree: u8, line: impl Iterator<Item = &'a u8>) -> (bool, u32) {
+    let mut seen = 0;
+    for &other in line {
+        seen += 1;
+        if other >= tree {
+            return (false, seen);
+        }
+    }
+    (true, seen)
+}
+
+fn study_tree(grid: &Grid<u8>, (row, col): Pos) -> (bool, u32) {
+    let tree = grid[(row, col)];
+    let row_cells = grid.row(row);
+    let column = grid.column(col).copied().collect::<Vec<_>>();
+    let views = [
+        look(tree, row_cells[..col].iter().rev()),
+        look(tree, row_cells[col + 1..].iter()),
+        look(tree, column[..row].iter().rev()),
+        look(tree, column[row + 1..].iter()),
+    ];
+    (
+        views.iter().any(|&(visible, _)| visible),
+        views.iter().map(|&(_, seen)| seen).product(),
+    )
+}
+
+// (visible from outside the grid, scenic score) of every tree not on the edge
+fn study_forest(grid: &Grid<u8>) -> Vec<(bool, u32)> {
+    let mut trees = vec![];
+    for r in 1..grid.rows() - 1 {
+        for c in 1..grid.cols() - 1 {
+            trees.push(study_tree(grid, (r, c)));
+        }
+    }
+    trees
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Grid<u8>;
+    type Output1 = usize;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Grid::parse(input, |b| b.is_ascii_digit().then(|| b - b'0'), "a tree height 0-9")
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize> {
+        let visible_trees = study_forest(grid)
+            .into_iter()
+            .filter(|&(visible, _)| visible)
+            .count();
+        // every tree on the edge is visible
+        let inside = grid.rows().saturating_sub(2) * grid.cols().saturating_sub(2);
+        Ok(visible_trees + grid.rows() * grid.cols() - inside)
+    }
+
+    fn part2(grid: &Self::Input) -> Result<u32> {
+        Ok(study_forest(grid)
+            .into_iter()
+            .map(|(_, scenic_score)| scenic_score)
+            .max()
+            // trees on the edge see nothing in at least one direction
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "30373\n25512\n65332\n33549\n35390\n";
+
+    #[test]
+    fn example() {
+        let trees = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part1(&trees).unwrap(), 21);
+        assert_eq!(Day08::part2(&trees).unwrap(), 8);
+    }
+
+    #[test]
+    fn forests_without_inner_trees() {
+        for (input, visible) in [("5\n", 1), ("123\n", 3), ("12\n34\n", 4)] {
+            let trees = Day08::parse(input).unwrap();
+            assert_eq!(Day08::part1(&trees).unwrap(), visible);
+            assert_eq!(Day08::part2(&trees).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn scenic_score_of_example_tree() {
+        let grid = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(study_tree(&grid, (3, 2)), (true, 8));
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+fn simulate_rope(instructions: &[(String, u8)], n: usize) -> usize {
+    let mut nodes = vec![(0, 0); n];
+    let mut visited = HashSet::new();
+
+    for (direction, amount) in instructions {
+        let delta = {
+            match direction.as_str() {
+                "U" => (0, 1),
+                "D" => (0, -1),
+                "L" => (-1, 0),
+                "R" => (1, 0),
+                _ => (0, 0)
+            }
+        };
+
+        for _ in 0..*amount {
+            nodes[0].0 += delta.0;
+            nodes[0].1 += delta.1;
+
+            for i in 1..n {
+                let dx: i32 = nodes[i-1].0 - nodes[i].0;
+                let dy: i32 = nodes[i-1].1 - nodes[i].1;
+
+                if dx.abs() > 1 || dy.abs() > 1 {
+                    if dx.abs() >= 1 {
+                        nodes[i].0 += (dx / dx.abs()) as i32 
+                    }
+                    if dy.abs() >= 1 {
+                        nodes[i].1 += (dy / dy.abs()) as i32
+                    }
+                }
+
+            }
+            visited.insert(nodes.last().cloned());
+        }
+    }
+    visited.len()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<(String, u8)>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|l| {
+                let (direction, amount) = parse::split_once(input, l, " ")?;
+                if !["U", "D", "L", "R"].contains(&direction) {
+                    return Err(Error::at(input, direction, format!("unknown direction `{direction}`, expected U, D, L or R")));
+                }
+                Ok((direction.to_string(), parse::number::<u8>(input, amount)?))
+            })
+            .collect()
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<usize> {
+        Ok(simulate_rope(instructions, 2))
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<usize> {
+        Ok(simulate_rope(instructions, 10))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let instructions = Day09::parse("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n").unwrap();
+        assert_eq!(Day09::part1(&instructions).unwrap(), 13);
+        assert_eq!(Day09::part2(&instructions).unwrap(), 1);
+    }
+
+    #[test]
+    fn larger_example() {
+        let instructions = Day09::parse("R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n").unwrap();
+        assert_eq!(Day09::part2(&instructions).unwrap(), 36);
+    }
+}
@@ -0,0 +1,281 @@
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+fn instruction_routine(
+    n: usize, // how many cpu cycles instruction takes
+    cycle: &mut i32, // pointer to current cycle number
+    x: &mut i32, // pointer to register value
+    register_values: &mut Vec<i32>,
+    display_rows: &mut Vec<String>,
+    display_row: &mut String
+) 
+{
+    for _ in 0..n {
+        // start of cycle -> draw pixel
+        display_row.push(
+            if [*x - 1, *x, *x + 1].contains(&(display_row.len() as i32)) {
+                '#'
+            }
+            else {
+                '.'
+            }
+        );
+        
+        // store signal strenght during these cycles
+        if [20, 60, 100, 140, 180, 220].iter().any(|r| r == cycle) {
+            let signal_strenght = *cycle * *x;
+            register_values.push(signal_strenght);
+        }
+        // new cycle begins here
+        *cycle += 1;
+
+        if (*cycle - 1) % 40 == 0 {
+            // display row is complete store it and start new one
+            display_rows.push(display_row.clone());
+            display_row.clear();
+        }
+    }
+}
+
+// (signal strengths at the sampled cycles, rendered display rows)
+fn run_program(instructions: &[Option<i32>]) -> (Vec<i32>, Vec<String>) {
+    let mut register_values = vec![];
+    let mut cycle = 1;
+    let mut x = 1;
+    let mut display_rows = vec![];
+    let mut display_row = String::new();
+
+    for instruction in instructions {
+        if let Some(incr) = instruction {
+            // addx
+            instruction_routine(2, &mut cycle, &mut x, &mut register_values, &mut display_rows, &mut display_row);
+            x += incr;
+        } else {
+            // noop
+            instruction_routine(1, &mut cycle, &mut x, &mut register_values, &mut display_rows, &mut display_row);
+        }
+    }
+    (register_values, display_rows)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    // addx operands, None for noop
+    type Input = Vec<Option<i32>>;
+    type Output1 = i32;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|instruction| {
+                match instruction.split_once(' ') {
+                    Some(("addx", incr)) => Ok(Some(parse::number::<i32>(input, incr)?)),
+                    None if instruction == "noop" => Ok(None),
+                    _ => Err(Error::at(input, instruction, format!("unknown instruction `{instruction}`"))),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<i32> {
+        let (register_values, _) = run_program(instructions);
+        Ok(register_values.iter().sum::<i32>())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<String> {
+        let (_, display_rows) = run_program(instructions);
+        Ok(display_rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    #[test]
+    fn example() {
+        let program = Day10::parse(EXAMPLE).unwrap();
+        let (register_values, _) = run_program(&program);
+        assert_eq!(register_values, vec![420, 1140, 1800, 2940, 2880, 3960]);
+        assert_eq!(Day10::part1(&program).unwrap(), 13140);
+        let screen = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+        assert_eq!(Day10::part2(&program).unwrap(), screen);
+    }
+
+    #[test]
+    fn constant_register() {
+        // x stays 1, so the signal strength is just the cycle number
+        let program = Day10::parse(&"noop\n".repeat(240)).unwrap();
+        assert_eq!(Day10::part1(&program).unwrap(), 20 + 60 + 100 + 140 + 180 + 220);
+        let row = format!("###{}", ".".repeat(37));
+        assert_eq!(Day10::part2(&program).unwrap(), [row.as_str(); 6].join("\n"));
+    }
+
+    #[test]
+    fn addx_takes_two_cycles() {
+        // x is 1 during cycles 1-3, 4 during cycles 4-5 and -1 from cycle 6 on
+        let program = Day10::parse(&format!("noop\naddx 3\naddx -5\n{}", "noop\n".repeat(20))).unwrap();
+        let (register_values, _) = run_program(&program);
+        assert_eq!(register_values, vec![-20]);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+#[derive(Clone, Debug)]
+enum WorryAdjustOperation {
+    Add(u64),
+    Mul(u64),
+    Pow2,
+}
+
+
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    items: Vec<u64>,
+    operation: WorryAdjustOperation,
+    test_divisible: u64,
+    throws_to: (usize, usize)
+}
+
+
+// the text after `marker` on the next line of a monkey's description
+fn next_field<'a>(
+    task_input: &str,
+    monkey: &'a str,
+    mk_iter: &mut impl Iterator<Item = &'a str>,
+    marker: &str,
+) -> Result<&'a str> {
+    let line = mk_iter.next().ok_or_else(|| {
+        Error::at(task_input, &monkey[monkey.len()..], format!("expected a line containing `{marker}`"))
+    })?;
+    Ok(parse::split_once(task_input, line, marker)?.1)
+}
+
+fn parse_monkeys_from_txt(task_input: &str) -> Result<Vec<Monkey>> {
+    let monkeys = parse::blocks(task_input)
+    .map(|monkey| {
+        let mut mk_iter = monkey.lines().skip(1);
+        let items = next_field(task_input, monkey, &mut mk_iter, ": ")?;
+        let items = items.split(", ").map(|i| parse::number(task_input, i)).collect::<Result<Vec<u64>>>()?;
+
+        let operation = next_field(task_input, monkey, &mut mk_iter, "= ")?;
+        let operation = match operation.split(' ').skip(1).collect::<Vec<_>>()[..] {
+            ["+", amount] => WorryAdjustOperation::Add(parse::number(task_input, amount)?),
+            ["*", "old"] => WorryAdjustOperation::Pow2,
+            ["*", amount] => WorryAdjustOperation::Mul(parse::number(task_input, amount)?),
+            _ => return Err(Error::at(task_input, operation, format!("unsupported operation `{operation}`"))),
+        };
+
+        let test_divisible = next_field(task_input, monkey, &mut mk_iter, "by ")?;
+        let test_divisible = match parse::number(task_input, test_divisible)? {
+            0 => return Err(Error::at(task_input, test_divisible, "cannot test divisibility by 0")),
+            n => n,
+        };
+
+        let throws_to = {
+            let if_true = next_field(task_input, monkey, &mut mk_iter, "monkey ")?;
+            let if_false = next_field(task_input, monkey, &mut mk_iter, "monkey ")?;
+            (parse::number::<usize>(task_input, if_true)?,
+            parse::number::<usize>(task_input, if_false)?)
+        };
+
+        Ok(Monkey{ items, operation, test_divisible, throws_to})
+    })
+    .collect::<Result<Vec<Monkey>>>()?;
+
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for target in [monkey.throws_to.0, monkey.throws_to.1] {
+            if target >= monkeys.len() {
+                return Err(Error::Invalid(format!("monkey {i} throws to monkey {target}, which does not exist")));
+            }
+        }
+    }
+    Ok(monkeys)
+}
+
+
+fn keep_away_game(mut monkeys: Vec<Monkey>, rounds: usize, modulus: u64) -> u64 {
+    let mut monkeys_inspected_times: Vec<u64> = vec![0; monkeys.len()];
+    for _ in 0..rounds {
+        for i in 0..monkeys.len() {
+            let mut mk = monkeys[i].clone();
+            while let Some(item) = mk.items.pop() {
+                let mut worry_level = match mk.operation {
+                    WorryAdjustOperation::Add(n) => item + n,
+                    WorryAdjustOperation::Mul(n) => item * n,
+                    WorryAdjustOperation::Pow2 => item.pow(2)
+                };
+
+                worry_level = if modulus == 0 {worry_level / 3} else {worry_level % modulus};
+
+                if worry_level % mk.test_divisible == 0 {
+                    monkeys[mk.throws_to.0].items.push(worry_level);
+                } else {
+                    monkeys[mk.throws_to.1].items.push(worry_level);
+                }
+                monkeys[i].items.clear();
+                monkeys_inspected_times[i] += 1;
+            }
+        }
+    }
+    monkeys_inspected_times.sort();
+    monkeys_inspected_times.iter().rev().take(2).product()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<Monkey>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_monkeys_from_txt(input)
+    }
+
+    fn part1(monkeys: &Self::Input) -> Result<u64> {
+        Ok(keep_away_game(monkeys.clone(), 20, 0))
+    }
+
+    fn part2(monkeys: &Self::Input) -> Result<u64> {
+        let modulus = monkeys.iter().map(|mk| mk.test_divisible).product::<u64>();
+        Ok(keep_away_game(monkeys.clone(), 10000, modulus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn example() {
+        let monkeys = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&monkeys).unwrap(), 10605);
+        assert_eq!(Day11::part2(&monkeys).unwrap(), 2713310158);
+    }
+
+    #[test]
+    fn rejects_throws_to_missing_monkeys() {
+        let input = EXAMPLE.replace("throw to monkey 3", "throw to monkey 7");
+        assert!(matches!(Day11::parse(&input), Err(Error::Invalid(_))));
+    }
+}
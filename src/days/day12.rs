@@ -0,0 +1,95 @@
+use crate::error::{Error, Result};
+use crate::grid::{Grid, Pos};
+use crate::search;
+use crate::solution::Solution;
+
+fn elevation(b: u8) -> u8 {
+    // start and end squares have elevations a and z
+    match b {
+        b'S' => b'a',
+        b'E' => b'z',
+        b => b,
+    }
+}
+
+fn get_neighbours<'a>(grid: &'a Grid<u8>, node: &Pos) -> impl Iterator<Item = Pos> + 'a {
+    // at most one step up, but any distance down
+    let node_height = elevation(grid[*node]);
+    grid.neighbours4(*node)
+        .filter(move |&nb| elevation(grid[nb]) <= node_height + 1)
+}
+
+// shortest path to E from whichever of `starts` is closest
+fn breadth_first_search(grid: &Grid<u8>, starts: impl IntoIterator<Item = Pos>) -> Option<Vec<Pos>> {
+    search::multi_source_bfs(
+        starts,
+        |node| get_neighbours(grid, node),
+        |&node| grid[node] == b'E',
+    )
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Grid<u8>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let grid = Grid::parse(
+            input,
+            |b| (b.is_ascii_lowercase() || b == b'S' || b == b'E').then_some(b),
+            "an elevation a-z, or S or E",
+        )?;
+        if grid.position(|&b| b == b'S').is_none() || grid.position(|&b| b == b'E').is_none() {
+            return Err(Error::Invalid("the map needs both a start S and an end E".to_string()));
+        }
+        Ok(grid)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize> {
+        // there is an S, parse checked
+        let start_loc = grid.position(|&b| b == b'S').unwrap();
+        let path = breadth_first_search(grid, [start_loc])
+            .ok_or_else(|| Error::Invalid("no path leads from S to E".to_string()))?;
+        Ok(path.len() - 1)
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize> {
+        let start_locs = grid
+            .iter()
+            .filter(|&(_, &b)| elevation(b) == b'a')
+            .map(|(loc, _)| loc);
+        let path = breadth_first_search(grid, start_locs)
+            .ok_or_else(|| Error::Invalid("no path leads from elevation a to E".to_string()))?;
+        Ok(path.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+
+    #[test]
+    fn example() {
+        let heightmap = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&heightmap).unwrap(), 31);
+        assert_eq!(Day12::part2(&heightmap).unwrap(), 29);
+    }
+
+    #[test]
+    fn requires_start_and_end() {
+        assert!(matches!(Day12::parse("abc\nabz\n"), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn reports_unreachable_ends() {
+        let heightmap = Day12::parse("SbE\n").unwrap();
+        assert_eq!(Day12::part1(&heightmap).unwrap_err().to_string(), "no path leads from S to E");
+        assert!(Day12::part2(&heightmap).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::newline,
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, separated_pair},
+    *,
+};
+
+use std::cmp::Ordering::{self, *};
+
+use crate::error::Result;
+use crate::parse;
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub struct Pair {
+    left: Packet,
+    right: Packet,
+}
+
+#[derive(Debug, Eq)]
+pub enum Packet {
+    Number(i32),
+    List(Vec<Packet>),
+}
+
+
+fn parse_packet(input: &str) -> IResult<&str, Packet> {
+    alt((
+        delimited(
+            tag("["),
+            separated_list0(tag(","), parse_packet),
+            tag("]"),
+        )
+        .map(Packet::List),
+        nom::character::complete::i32
+            .map(Packet::Number),
+    ))(input)
+}
+
+fn parse_pairs(input: &str) -> IResult<&str, Vec<Pair>> {
+    separated_list1(
+        tag("\n\n"),
+        separated_pair(parse_packet, newline, parse_packet)
+            .map(|(left, right)| Pair { left, right },
+    ))(input)
+}
+
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::List(left), Self::List(right)) => left == right,
+            (Self::Number(left), Self::Number(right)) => left == right,
+            (Self::List(left), Self::Number(right)) => left == &vec![Packet::Number(*right)],
+            (Self::Number(left), Self::List(right)) => &vec![Packet::Number(*left)] == right,
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self,other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            (Packet::List(a), Packet::Number(b)) => a.cmp(&vec![Packet::Number(*b)]),
+            (Packet::Number(a), Packet::List(b)) => vec![Packet::Number(*a)].cmp(b),
+            (Packet::Number(a), Packet::Number(b)) => a.cmp(b),
+        }
+    }
+}
+
+fn pt1_pairs_in_correct_order(pairs: &[Pair]) -> Vec<usize> {
+    pairs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, Pair { left, right })| {
+            match left.cmp(right)
+            {
+                Less | Equal => Some(i + 1),
+                Greater => None,
+            }
+        })
+        .collect::<Vec<usize>>()
+}
+
+fn pt2_all_packets(pairs: &[Pair]) -> Vec<&Packet> {
+    let packets: Vec<&Packet> = pairs
+        .iter()
+        .flat_map(|Pair { left, right }| [left, right])
+        .collect();
+    packets
+}
+
+fn pt2_calculate_decoder_key(packets: Vec<&Packet>) -> usize {
+    let divider_packet_2 = Packet::List(vec![Packet::Number(2)]);
+    let divider_packet_6 = Packet::List(vec![Packet::Number(6)]);
+    let divider_packet_2_index = packets
+        .iter()
+        .position(|packet| packet == &&divider_packet_2);
+
+    let divider_packet_6_index = packets
+        .iter()
+        .position(|packet| packet == &&divider_packet_6);
+
+    match (divider_packet_2_index, divider_packet_6_index) {
+        (Some(pos1), Some(pos2)) => (pos1 + 1) * (pos2 + 1),
+        _ => panic!("divider packets not found"),
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<Pair>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::finish(input, parse_pairs(input), "a pair of packets such as `[1,[2]]` and `[3]`")
+    }
+
+    fn part1(pairs: &Self::Input) -> Result<usize> {
+        let in_correct_order = pt1_pairs_in_correct_order(pairs);
+        Ok(in_correct_order.iter().sum::<usize>())
+    }
+
+    fn part2(pairs: &Self::Input) -> Result<usize> {
+        let mut all_packets = pt2_all_packets(pairs);
+        let divider_packet_2 = Packet::List(vec![Packet::Number(2)]);
+        let divider_packet_6 = Packet::List(vec![Packet::Number(6)]);
+        all_packets.push(&divider_packet_2);
+        all_packets.push(&divider_packet_6);
+        all_packets.sort();
+
+        Ok(pt2_calculate_decoder_key(all_packets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    #[test]
+    fn example() {
+        let pairs = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(pt1_pairs_in_correct_order(&pairs), vec![1, 2, 4, 6]);
+        assert_eq!(Day13::part1(&pairs).unwrap(), 13);
+        assert_eq!(Day13::part2(&pairs).unwrap(), 140);
+    }
+
+    #[test]
+    fn mixed_comparison() {
+        let (_, left) = parse_packet("[[1],[2,3,4]]").unwrap();
+        let (_, right) = parse_packet("[[1],4]").unwrap();
+        assert_eq!(left.cmp(&right), Less);
+    }
+
+    #[test]
+    fn reports_unbalanced_packets() {
+        let err = Day13::parse("[1,2]\n[3\n").unwrap_err();
+        assert!(err.to_string().starts_with("line 2, column 1:"), "{err}");
+    }
+}
@@ -0,0 +1,197 @@
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag, character::complete,
+    character::complete::newline,
+    multi::separated_list1, sequence::separated_pair, *,
+};
+
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::parse;
+use crate::solution::Solution;
+
+
+fn one_line(input: &str) -> IResult<&str, impl Iterator<Item = (u32, u32)>> {
+    let (input, pairs) = separated_list1(
+        tag(" -> "),
+        separated_pair(
+            complete::u32,
+            complete::char(','),
+            complete::u32,
+        ),
+    )(input)?;
+    let rocks_from_one_line = pairs.into_iter().tuple_windows().flat_map(
+        |((ax, ay), (bx, by))| {
+            let x_min = ax.min(bx);
+            let x_max = ax.max(bx);
+            let x_range = x_min..=x_max;
+
+            let y_min = ay.min(by);
+            let y_max = ay.max(by);
+            let y_range = y_min..=y_max;
+            x_range.cartesian_product(y_range)
+        },
+    );
+    Ok((input, rocks_from_one_line))
+}
+
+fn parse_initial_grid(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
+    let (input, rocks_from_all_lines) = separated_list1(newline, one_line)(input)?;
+    Ok((input, rocks_from_all_lines.into_iter().flatten().collect()))
+}
+
+const SAND_SOURCE: (usize, usize) = (500, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// Slice of the cave wide enough for any sand poured from the source, indexed by `(y, col)`
+/// with the source in the middle column, so the slice may reach left of x = 0.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    grid: Grid<Tile>,
+    // (col, y) of the source
+    source: (usize, usize),
+    lowest_rocks: usize,
+}
+
+impl Cave {
+    fn new(rocks: &[(u32, u32)]) -> Option<Self> {
+        let lowest_rocks = rocks.iter().map(|&(_, y)| y as usize).max()?;
+        // sand spreads at most one step sideways per step down, and never past the floor
+        let floor = lowest_rocks + 2;
+        let source = (floor + 1, SAND_SOURCE.1);
+        let mut grid = Grid::new(floor + 1, 2 * floor + 3, Tile::Air);
+        for &(x, y) in rocks {
+            let col = (x as usize + source.0).checked_sub(SAND_SOURCE.0);
+            if let Some(tile) = col.and_then(|col| grid.get_mut((y as usize, col))) {
+                *tile = Tile::Rock;
+            }
+        }
+        Some(Self { grid, source, lowest_rocks })
+    }
+
+    fn floor(&self) -> usize {
+        self.lowest_rocks + 2
+    }
+
+    fn tile(&self, (col, y): (usize, usize)) -> Tile {
+        self.grid[(y, col)]
+    }
+
+    fn set(&mut self, (col, y): (usize, usize), tile: Tile) {
+        self.grid[(y, col)] = tile;
+    }
+
+    fn add_floor(&mut self) {
+        let floor = self.floor();
+        for col in 0..self.grid.cols() {
+            self.grid[(floor, col)] = Tile::Rock;
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Part {
+    PT1,
+    PT2,
+}
+
+struct SandDropSimulation {
+    cave: Cave,
+    part: Part
+}
+
+impl SandDropSimulation {
+    // units of sand dropped before the part's stop condition is met
+    fn execute(&mut self) -> usize {
+        if let Part::PT2 = self.part {
+            self.cave.add_floor();
+        }
+        // part 2 stops once the source is blocked
+        while self.cave.tile(self.cave.source) == Tile::Air {
+            match self.drop_sand() {
+                Some(rest) => self.cave.set(rest, Tile::Sand),
+                None => break,
+            }
+        }
+        self.cave.grid.iter().filter(|&(_, &tile)| tile == Tile::Sand).count()
+    }
+
+    // where a unit of sand comes to rest, or None if it falls past the lowest rocks into the abyss
+    fn drop_sand(&self) -> Option<(usize, usize)> {
+        let (mut x, mut y) = self.cave.source;
+        loop {
+            if y + 1 == self.cave.floor() && self.cave.tile((x, y + 1)) == Tile::Air {
+                return None;
+            }
+            // free fall, else spread left, else spread right
+            let next = [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+                .into_iter()
+                .find(|&location| self.cave.tile(location) == Tile::Air);
+            match next {
+                Some(location) => (x, y) = location,
+                None => return Some((x, y)),
+            }
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Cave;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let rocks = parse::finish(input, parse_initial_grid(input), "a rock path such as `498,4 -> 498,6`")?;
+        Cave::new(&rocks).ok_or_else(|| Error::Invalid("rock paths need at least two points".to_string()))
+    }
+
+    fn part1(cave: &Self::Input) -> Result<usize> {
+        let mut pt_1_simulation = SandDropSimulation {
+            cave: cave.clone(),
+            part: Part::PT1
+        };
+        Ok(pt_1_simulation.execute())
+    }
+
+    fn part2(cave: &Self::Input) -> Result<usize> {
+        let mut pt_2_simulation = SandDropSimulation {
+            cave: cave.clone(),
+            part: Part::PT2
+        };
+        Ok(pt_2_simulation.execute())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+
+    #[test]
+    fn example() {
+        let cave = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(cave.lowest_rocks, 9);
+        assert_eq!(Day14::part1(&cave).unwrap(), 24);
+        assert_eq!(Day14::part2(&cave).unwrap(), 93);
+    }
+
+    #[test]
+    fn caves_deeper_than_the_source_is_far_from_x_0() {
+        // the floor is 602 deep, so sand piles up from x = -101 to 1101, all but the rocks
+        // and the unit they shelter
+        let cave = Day14::parse("500,600 -> 502,600\n").unwrap();
+        assert_eq!(Day14::part1(&cave).unwrap(), 0);
+        assert_eq!(Day14::part2(&cave).unwrap(), 602 * 602 - 4);
+    }
+}
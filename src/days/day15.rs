@@ -0,0 +1,174 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+    *,
+};
+
+use crate::geom::{self, Range};
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+// Custom types
+const PT1_ROW: i64 = 2000000;
+const PT2_SEARCH_BASE: i64 = 4000000;
+
+#[derive(Debug, Clone)]
+pub struct Beacon {
+    x: i64,
+    y: i64,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Sensor {
+    x: i64,
+    y: i64,
+}
+
+impl Sensor {
+    fn distance_to_beacon(&self, beacon: &Beacon) -> u64 {
+        geom::manhattan((self.x, self.y), (beacon.x, beacon.y))
+    }
+    fn distance_to_row(&self, row: i64) -> u64 {
+        self.y.abs_diff(row)
+    }
+}
+
+// Parsing
+fn position(input: &str) -> IResult<&str, (i64, i64)> {
+    separated_pair(
+        preceded(tag("x="), complete::i64),
+        tag(", "),
+        preceded(tag("y="), complete::i64),
+    )(input)
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<(Sensor, Beacon)>> {
+    let (input, map) = separated_list1(
+        complete::line_ending,
+        preceded(
+            tag("Sensor at "),
+            separated_pair(
+                position.map(|(x, y)| Sensor { x, y }),
+                tag(": closest beacon is at "),
+                position.map(|(x, y)| Beacon { x, y }),
+            )
+        ),
+    )(input)?;
+    Ok((input, map))
+}
+
+// Solution
+fn range_at_given_row(sensor: &Sensor, beacon: &Beacon, row: i64) -> Option<Range> {
+    let sensor_beacon_distance = sensor.distance_to_beacon(beacon) as i64;
+    let sensor_row_distance = sensor.distance_to_row(row) as i64;
+    if sensor_row_distance > sensor_beacon_distance {
+        return None;
+    }
+    let delta_x = sensor_beacon_distance.abs_diff(sensor_row_distance) as i64;
+    let range = Range::new(sensor.x - delta_x, sensor.x + delta_x);
+
+    Some(range)
+}
+
+fn ranges_without_beacon_at_row(
+    sensor_beacon_pairs: &[(Sensor, Beacon)],
+    row: i64
+) -> Vec<Range> {
+    let ranges = sensor_beacon_pairs
+        .iter()
+        .filter_map(|(sensor, beacon)| range_at_given_row(sensor, beacon, row));
+    geom::merge_ranges(ranges)
+}
+
+fn pt1_sum_ranges(ranges: Vec<Range>) -> u64 {
+    ranges
+        .into_iter()
+        .map(|range| (range.max - range.min) as u64)
+        .sum()
+}
+
+fn pt2_find_isolated_beacon(
+    sensor_beacon_pairs: &[(Sensor, Beacon)],
+    search_base: i64
+) -> Option<Beacon> {
+    for i in 0..search_base + 1 {
+        let ranges = ranges_without_beacon_at_row(sensor_beacon_pairs, i);
+        if ranges.len() > 1 {
+            let hidden_beacon_x = (ranges[1].min + ranges[0].max).div_euclid(2);
+            let hidden_beacon = Beacon { x: hidden_beacon_x, y: i };
+            return Some(hidden_beacon)
+        }
+    }
+    None
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<(Sensor, Beacon)>;
+    type Output1 = u64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::finish(
+            input,
+            parse_input(input),
+            "`Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>`",
+        )
+    }
+
+    fn part1(sensor_beacon_pairs: &Self::Input) -> Result<u64> {
+        let ranges = ranges_without_beacon_at_row(sensor_beacon_pairs, PT1_ROW);
+        Ok(pt1_sum_ranges(ranges))
+    }
+
+    fn part2(sensor_beacon_pairs: &Self::Input) -> Result<i64> {
+        let hidden_beacon = pt2_find_isolated_beacon(sensor_beacon_pairs, PT2_SEARCH_BASE)
+            .ok_or_else(|| Error::Invalid(format!("no spot within 0-{PT2_SEARCH_BASE} is out of every sensor's reach")))?;
+        // tuning frequency
+        Ok(hidden_beacon.x * 4000000 + hidden_beacon.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn example_row_10() {
+        let pairs = Day15::parse(EXAMPLE).unwrap();
+        let ranges = ranges_without_beacon_at_row(&pairs, 10);
+        assert_eq!(ranges, vec![Range::new(-2, 24)]);
+        assert_eq!(pt1_sum_ranges(ranges), 26);
+    }
+
+    #[test]
+    fn example_isolated_beacon() {
+        let pairs = Day15::parse(EXAMPLE).unwrap();
+        let beacon = pt2_find_isolated_beacon(&pairs, 20).unwrap();
+        assert_eq!((beacon.x, beacon.y), (14, 11));
+    }
+}
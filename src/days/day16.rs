@@ -0,0 +1,796 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Write};
+
+use itertools::Itertools;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::search;
+use crate::solution::Solution;
+
+// parsing
+fn parse_input(input: &str) -> Result<Vec<(&str, u32, Vec<&str>)>> {
+    input.lines().map(|line| parse_line(input, line)).collect()
+}
+
+fn parse_line<'a>(input: &str, line: &'a str) -> Result<(&'a str, u32, Vec<&'a str>)> {
+    let mut parts_to_take = line
+        .split(not_uppercase_or_numeric)
+        .filter(|s| !s.is_empty())
+        .skip(1);
+
+    let (Some(name), Some(flow_rate)) = (parts_to_take.next(), parts_to_take.next()) else {
+        return Err(Error::at(input, line, "expected `Valve <name> has flow rate=<rate>; tunnels lead to valves <names>`"));
+    };
+    let flow_rate = parse::number(input, flow_rate)?;
+    let neighbours = parts_to_take.collect();
+
+    Ok((name, flow_rate, neighbours))
+}
+
+fn not_uppercase_or_numeric(c: char) -> bool {
+    !c.is_uppercase() && !c.is_ascii_digit()
+}
+
+// solution
+fn shortest_distance_between_valves(tunnels: &[Vec<usize>]) -> Vec<Vec<u32>> {
+    // every tunnel takes a minute, unreachable valves are u32::MAX minutes away
+    let indices = (0..tunnels.len()).collect::<Vec<_>>();
+    search::all_pairs_shortest_paths(&indices, |&i| tunnels[i].iter().map(|&neighbour| (neighbour, 1)))
+    .into_iter()
+    .map(|row| row.into_iter().map(|distance| distance.unwrap_or(u32::MAX)).collect())
+    .collect()
+}
+
+
+// Valves with a non-zero flow rate, renumbered 0.. so a set of them fits in a u64 bitmask,
+// followed by the start valve unless it is one of them
+struct ValveNetwork {
+    // index into `Valves` of every position
+    indices: Vec<usize>,
+    flow_rates: Vec<u32>,
+    distances: Vec<Vec<u32>>,
+    // valves worth opening, the first positions
+    openable: usize,
+    start: usize,
+}
+
+impl ValveNetwork {
+    fn new(valves: &Valves, i_start: usize) -> Self {
+        let mut positions = (0..valves.flow_rates.len())
+            .filter(|&i| valves.flow_rates[i] > 0)
+            .collect::<Vec<_>>();
+        let openable = positions.len();
+        let start = positions.iter().position(|&i| i == i_start).unwrap_or_else(|| {
+            positions.push(i_start);
+            openable
+        });
+        Self {
+            flow_rates: positions.iter().map(|&i| valves.flow_rates[i]).collect(),
+            distances: positions
+                .iter()
+                .map(|&from| positions.iter().map(|&to| valves.distances[from][to]).collect())
+                .collect(),
+            openable,
+            start,
+            indices: positions,
+        }
+    }
+
+    // valves worth opening, which may include the start
+    fn valves(&self) -> impl Iterator<Item = usize> + Clone {
+        0..self.openable
+    }
+
+    // minutes to walk from `from` to `to` and open it
+    fn time_to_open(&self, from: usize, to: usize) -> u32 {
+        self.distances[from][to].saturating_add(1)
+    }
+}
+
+// valves an agent opens in order, with the minutes left after opening each
+type Route = Vec<(usize, u32)>;
+
+// branch and bound over the valve every agent opens next, always moving the agent with the
+// most minutes left, dropping states reached before with at least as much pressure released:
+// the same positions and minutes left, whichever agent has which, and the same valves opened.
+// Used for one agent, for more than two, and for two once there are too many valves for
+// `pair_opened_sets`
+struct ValveSearch<'a> {
+    network: &'a ValveNetwork,
+    // valves worth opening, highest flow rate first
+    by_flow_rate: Vec<usize>,
+    // fewest minutes between opening one valve and opening another
+    min_step: u32,
+    // most pressure released so far on reaching each state, keyed by the sorted position and
+    // minutes left of every agent and the valves opened
+    seen: HashMap<(Vec<(usize, u32)>, u64), u32>,
+    best: (u32, Vec<Route>),
+}
+
+impl<'a> ValveSearch<'a> {
+    fn new(network: &'a ValveNetwork) -> Self {
+        let by_flow_rate = network.valves().sorted_by_key(|&valve| Reverse(network.flow_rates[valve])).collect();
+        let min_step = network
+            .valves()
+            .tuple_combinations()
+            .map(|(a, b)| network.time_to_open(a, b).min(network.time_to_open(b, a)))
+            .min()
+            .unwrap_or(u32::MAX);
+        Self { network, by_flow_rate, min_step, seen: HashMap::new(), best: (0, vec![]) }
+    }
+
+    // most pressure `agents` agents release in `time_limit` minutes from the start, with
+    // the route of each
+    fn max_release(mut self, time_limit: u32, agents: usize) -> (u32, Vec<Route>) {
+        self.best = (0, vec![vec![]; agents]);
+        let mut routes = vec![vec![]; agents];
+        self.explore(&mut vec![(self.network.start, time_limit); agents], 0, 0, &mut routes);
+        self.best
+    }
+
+    fn explore(&mut self, agents: &mut [(usize, u32)], opened: u64, released: u32, routes: &mut [Route]) {
+        if released > self.best.0 {
+            self.best = (released, routes.to_vec());
+        }
+        if released + self.upper_bound(agents, opened) <= self.best.0 {
+            return;
+        }
+        // agents start together, so swapping two of them leaves as much to release
+        let mut key = agents.to_vec();
+        key.sort_unstable();
+        match self.seen.entry((key, opened)) {
+            Entry::Occupied(entry) if *entry.get() >= released => return,
+            Entry::Occupied(mut entry) => *entry.get_mut() = released,
+            Entry::Vacant(entry) => {
+                entry.insert(released);
+            }
+        }
+
+        let (agent, (position, time_left)) =
+            agents.iter().copied().enumerate().max_by_key(|&(_, (_, time_left))| time_left).unwrap();
+        let mut next = self
+            .network
+            .valves()
+            .filter(|&valve| opened & (1 << valve) == 0)
+            .filter_map(|valve| Some((valve, time_left.checked_sub(self.network.time_to_open(position, valve))?)))
+            .filter(|&(_, valve_time_left)| valve_time_left > 0)
+            .collect::<Vec<_>>();
+        // the most pressure first, to find good totals early
+        next.sort_unstable_by_key(|&(valve, valve_time_left)| Reverse(self.network.flow_rates[valve] * valve_time_left));
+        for (valve, valve_time_left) in next {
+            agents[agent] = (valve, valve_time_left);
+            routes[agent].push((valve, valve_time_left));
+            let more = self.network.flow_rates[valve] * valve_time_left;
+            self.explore(agents, opened | (1 << valve), released + more, routes);
+            routes[agent].pop();
+        }
+        // or it stops and leaves the other valves to the other agents
+        if agents.len() > 1 && time_left > 0 {
+            agents[agent] = (position, 0);
+            self.explore(agents, opened, released, routes);
+        }
+        agents[agent] = (position, time_left);
+    }
+
+    // pressure still to release if the closed valves, highest flow rate first, were opened at
+    // the latest minutes any agent could open a valve: the soonest it can open one, then
+    // every `min_step` minutes after
+    fn upper_bound(&self, agents: &[(usize, u32)], opened: u64) -> u32 {
+        let closed = || self.by_flow_rate.iter().copied().filter(|&valve| opened & (1 << valve) == 0);
+        let mut slots = agents
+            .iter()
+            .map(|&(position, time_left)| {
+                let soonest = closed().map(|valve| self.network.time_to_open(position, valve)).min();
+                soonest.map_or(0, |soonest| time_left.saturating_sub(soonest))
+            })
+            .collect::<Vec<_>>();
+        let mut bound = 0;
+        for valve in closed() {
+            let slot = slots.iter_mut().max().unwrap();
+            if *slot == 0 {
+                break;
+            }
+            bound += self.network.flow_rates[valve] * *slot;
+            *slot = slot.saturating_sub(self.min_step);
+        }
+        bound
+    }
+}
+
+// most valves worth opening for which two agents pair their best opened sets; with more there
+// are too many sets once lots of valves lie a few minutes apart, as in
+// `every_valve_next_to_every_other`, and the agents are searched together instead
+const MAX_PAIRED_VALVES: usize = 16;
+
+// most pressure one agent releases for every set of valves it can open within `time_limit`,
+// with the route doing it; orders reaching the same valve at the same minute with the same
+// valves open but less released are dropped, as they end no better
+fn best_pressure_per_opened_set(network: &ValveNetwork, time_limit: u32) -> HashMap<u64, (u32, Route)> {
+    let mut best = HashMap::new();
+    let mut seen = HashMap::new();
+    open_valves(network, (network.start, time_limit), 0, 0, &mut vec![], &mut seen, &mut best);
+    best
+}
+
+fn open_valves(
+    network: &ValveNetwork,
+    (position, time_left): (usize, u32),
+    opened: u64,
+    released: u32,
+    route: &mut Route,
+    seen: &mut HashMap<(usize, u32, u64), u32>,
+    best: &mut HashMap<u64, (u32, Route)>,
+) {
+    match seen.entry((position, time_left, opened)) {
+        Entry::Occupied(entry) if *entry.get() >= released => return,
+        Entry::Occupied(mut entry) => *entry.get_mut() = released,
+        Entry::Vacant(entry) => {
+            entry.insert(released);
+        }
+    }
+    match best.entry(opened) {
+        Entry::Occupied(entry) if entry.get().0 >= released => {}
+        Entry::Occupied(mut entry) => *entry.get_mut() = (released, route.clone()),
+        Entry::Vacant(entry) => {
+            entry.insert((released, route.clone()));
+        }
+    }
+
+    for valve in network.valves().filter(|&valve| opened & (1 << valve) == 0) {
+        let Some(valve_time_left) = time_left.checked_sub(network.time_to_open(position, valve)).filter(|&t| t > 0) else {
+            continue;
+        };
+        route.push((valve, valve_time_left));
+        let more = network.flow_rates[valve] * valve_time_left;
+        open_valves(network, (valve, valve_time_left), opened | (1 << valve), released + more, route, seen, best);
+        route.pop();
+    }
+}
+
+// two agents opening disjoint sets of valves, each at its best for its set
+fn pair_opened_sets(network: &ValveNetwork, time_limit: u32) -> (u32, Vec<Route>) {
+    let mut single = best_pressure_per_opened_set(network, time_limit).into_iter().collect::<Vec<_>>();
+    single.sort_unstable_by_key(|&(_, (released, _))| Reverse(released));
+    let mut best = (0, [0, 0]);
+    for (i, &(yours, (released, _))) in single.iter().enumerate() {
+        // no later set can lift the total above the best found so far
+        if released + single[0].1.0 <= best.0 {
+            break;
+        }
+        if let Some(j) = single.iter().position(|&(theirs, _)| yours & theirs == 0) {
+            if released + single[j].1.0 > best.0 {
+                best = (released + single[j].1.0, [i, j]);
+            }
+        }
+    }
+    let (released, pair) = best;
+    (released, pair.iter().map(|&i| single[i].1.1.clone()).collect())
+}
+
+/// Variant of the puzzle: how long the agents have, where they all start and how many
+/// of them share the work of opening valves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub time_limit: u32,
+    /// `None` starts from AA, or from the first valve listed when there is no AA.
+    pub start: Option<String>,
+    pub agents: usize,
+}
+
+impl Config {
+    /// Alone for 30 minutes from AA.
+    pub fn part1() -> Self {
+        Self { time_limit: 30, start: None, agents: 1 }
+    }
+
+    /// With the elephant, after spending 4 of the 30 minutes teaching it.
+    pub fn part2() -> Self {
+        Self { time_limit: 26, start: None, agents: 2 }
+    }
+}
+
+/// A valve opened by one of the agents of a [`Schedule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    /// Numbered from 0.
+    pub agent: usize,
+    pub valve: String,
+    pub flow_rate: u32,
+    /// Minute spent opening the valve, counting from 1; it releases pressure from the next one.
+    pub minute: u32,
+    /// Valves walked through from the agent's previous valve to this one, both included.
+    pub path: Vec<String>,
+}
+
+/// Optimal way for the agents of a [`Config`] to release the most pressure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub time_limit: u32,
+    pub agents: usize,
+    pub released: u32,
+    /// Ordered by minute, then by agent.
+    pub openings: Vec<Opening>,
+}
+
+impl Schedule {
+    /// Total flow rate during every minute from 1 to the time limit, with the pressure released
+    /// up to and including that minute.
+    pub fn pressure_per_minute(&self) -> Vec<(u32, u32)> {
+        let mut released = 0;
+        (1..=self.time_limit)
+            .map(|minute| {
+                let flow_rate = self
+                    .openings
+                    .iter()
+                    .filter(|opening| opening.minute < minute)
+                    .map(|opening| opening.flow_rate)
+                    .sum::<u32>();
+                released += flow_rate;
+                (flow_rate, released)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let agents = if self.agents == 1 { "1 agent".to_string() } else { format!("{} agents", self.agents) };
+        writeln!(f, "{} pressure released in {} minutes by {agents}", self.released, self.time_limit)?;
+        writeln!(f)?;
+        for opening in &self.openings {
+            writeln!(
+                f,
+                "minute {:>2}: agent {} opens {} (flow rate {}) after walking {}",
+                opening.minute,
+                opening.agent + 1,
+                opening.valve,
+                opening.flow_rate,
+                opening.path.join(" -> "),
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "minute  flow rate  released")?;
+        for (minute, (flow_rate, released)) in (1..).zip(self.pressure_per_minute()) {
+            writeln!(f, "{minute:>6}  {flow_rate:>9}  {released:>8}")?;
+        }
+        Ok(())
+    }
+}
+
+// colours of the agents' routes in DOT exports
+const AGENT_COLOURS: [&str; 4] = ["red", "blue", "darkgreen", "orange"];
+
+fn agent_colour(agent: usize) -> &'static str {
+    AGENT_COLOURS[agent % AGENT_COLOURS.len()]
+}
+
+// undirected edge between two valves, keyed the same way whichever way it is walked
+fn edge(a: &str, b: &str) -> (String, String) {
+    let (from, to) = if a < b { (a, b) } else { (b, a) };
+    (from.to_string(), to.to_string())
+}
+
+// each agent's route as consecutive (from, to) valve names, taken from the paths walked
+// (`legs` false) or from one opened valve to the next (`legs` true)
+fn route_edges(schedule: &Schedule, legs: bool) -> BTreeMap<(String, String), usize> {
+    let mut edges = BTreeMap::new();
+    for opening in &schedule.openings {
+        let walked = if legs {
+            vec![opening.path[0].clone(), opening.valve.clone()]
+        } else {
+            opening.path.clone()
+        };
+        for (from, to) in walked.iter().tuple_windows() {
+            edges.insert(edge(from, to), opening.agent);
+        }
+    }
+    edges
+}
+
+#[derive(Debug)]
+pub struct Valves {
+    valve_names: Vec<String>,
+    flow_rates: Vec<u32>,
+    tunnels: Vec<Vec<usize>>,
+    distances: Vec<Vec<u32>>,
+}
+
+impl Valves {
+    /// Tunnel network in Graphviz DOT, one node per valve labelled with its flow rate. When a
+    /// `schedule` is given, the tunnels each agent walks and the valves it opens get its colour.
+    pub fn tunnels_dot(&self, schedule: Option<&Schedule>) -> String {
+        let mut edges = BTreeMap::new();
+        for (i, neighbours) in self.tunnels.iter().enumerate() {
+            for &j in neighbours {
+                edges.insert(edge(&self.valve_names[i], &self.valve_names[j]), None);
+            }
+        }
+        let names = (0..self.valve_names.len()).collect::<Vec<_>>();
+        self.dot("tunnels", &names, edges, schedule.map(|schedule| (schedule, false)))
+    }
+
+    /// Valves worth opening and the start valve, every pair joined by an edge labelled with the
+    /// minutes it takes to walk between them. A `schedule` highlights the order of openings.
+    pub fn compressed_dot(&self, start: Option<&str>, schedule: Option<&Schedule>) -> Result<String> {
+        let start_idx = self.start_index(start)?;
+        let network = ValveNetwork::new(self, start_idx);
+        let mut edges = BTreeMap::new();
+        for (&i, &j) in network.indices.iter().tuple_combinations() {
+            if self.distances[i][j] != u32::MAX {
+                edges.insert(edge(&self.valve_names[i], &self.valve_names[j]), Some(self.distances[i][j]));
+            }
+        }
+        Ok(self.dot("compressed", &network.indices, edges, schedule.map(|schedule| (schedule, true))))
+    }
+
+    // edges are (from, to) with from < to, labelled with their distance if any
+    fn dot(
+        &self,
+        name: &str,
+        valves: &[usize],
+        edges: BTreeMap<(String, String), Option<u32>>,
+        route: Option<(&Schedule, bool)>,
+    ) -> String {
+        let route_edges = route.map(|(schedule, legs)| route_edges(schedule, legs)).unwrap_or_default();
+        let opened_by = |valve: &str| {
+            let (schedule, _) = route?;
+            schedule.openings.iter().find(|opening| opening.valve == valve).map(|opening| opening.agent)
+        };
+
+        let mut dot = format!("graph {name} {{\n");
+        for &i in valves {
+            let valve = &self.valve_names[i];
+            let style = match opened_by(valve) {
+                Some(agent) => format!(", color={}, penwidth=2", agent_colour(agent)),
+                None => String::new(),
+            };
+            writeln!(dot, "    {valve} [label=\"{valve}\\n{}\"{style}];", self.flow_rates[i]).unwrap();
+        }
+        for (edge, distance) in edges {
+            let mut attributes = vec![];
+            if let Some(distance) = distance {
+                attributes.push(format!("label={distance}"));
+            }
+            if let Some(&agent) = route_edges.get(&edge) {
+                attributes.push(format!("color={}, penwidth=3", agent_colour(agent)));
+            }
+            let attributes = if attributes.is_empty() { String::new() } else { format!(" [{}]", attributes.join(", ")) };
+            writeln!(dot, "    {} -- {}{attributes};", edge.0, edge.1).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    // the named valve, or AA, or the first valve listed
+    fn start_index(&self, start: Option<&str>) -> Result<usize> {
+        match start {
+            Some(start) => self
+                .valve_names
+                .iter()
+                .position(|v| v == start)
+                .ok_or_else(|| Error::Invalid(format!("there is no start valve {start}"))),
+            None => Ok(self.valve_names.iter().position(|v| v == "AA").unwrap_or(0)),
+        }
+    }
+
+    /// Most pressure the agents of `config` can release together.
+    pub fn max_pressure(&self, config: &Config) -> Result<u32> {
+        Ok(self.schedule(config)?.released)
+    }
+
+    /// Which valve every agent of `config` opens when, releasing the most pressure.
+    pub fn schedule(&self, config: &Config) -> Result<Schedule> {
+        let start_idx = self.start_index(config.start.as_deref())?;
+        let network = ValveNetwork::new(self, start_idx);
+        if config.agents == 0 {
+            return Err(Error::Invalid("at least one agent has to open valves".to_string()));
+        }
+        let (released, routes) = if config.agents == 2 && network.openable <= MAX_PAIRED_VALVES {
+            pair_opened_sets(&network, config.time_limit)
+        } else {
+            ValveSearch::new(&network).max_release(config.time_limit, config.agents)
+        };
+
+        let mut openings = vec![];
+        for (agent, route) in routes.into_iter().enumerate() {
+            let mut from = start_idx;
+            for (valve, time_left) in route {
+                let to = network.indices[valve];
+                let path = search::bfs(from, |&i| self.tunnels[i].iter().copied(), |&i| i == to).unwrap();
+                openings.push(Opening {
+                    agent,
+                    valve: self.valve_names[to].clone(),
+                    flow_rate: self.flow_rates[to],
+                    minute: config.time_limit - time_left,
+                    path: path.into_iter().map(|i| self.valve_names[i].clone()).collect(),
+                });
+                from = to;
+            }
+        }
+        openings.sort_by_key(|opening| (opening.minute, opening.agent));
+        Ok(Schedule { time_limit: config.time_limit, agents: config.agents, released, openings })
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Valves;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(raw_input: &str) -> Result<Self::Input> {
+        let input = parse_input(raw_input)?;
+
+        let flow_rates = input.iter().map(|(_, flow_rate, _)| *flow_rate).collect::<Vec<u32>>();
+        let valve_names = input.iter().map(|(label, _, _)| *label).collect::<Vec<&str>>();
+        let neighbours = input
+            .iter()
+            .map(|(_, _, ref neighbours)| neighbours.clone())
+            .collect::<Vec<Vec<&str>>>();
+
+        if let Some(unknown) = neighbours.iter().flatten().find(|v| !valve_names.contains(v)) {
+            return Err(Error::at(raw_input, unknown, format!("tunnel leads to unknown valve `{unknown}`")));
+        }
+        if valve_names.is_empty() {
+            return Err(Error::Invalid("there are no valves".to_string()));
+        }
+        // one bit per valve worth opening
+        if flow_rates.iter().filter(|&&flow_rate| flow_rate > 0).count() > u64::BITS as usize {
+            return Err(Error::Invalid(format!("more than {} valves have a flow rate", u64::BITS)));
+        }
+
+        let tunnels = neighbours
+            .iter()
+            .map(|names| names.iter().map(|name| valve_names.iter().position(|v| v == name).unwrap()).collect())
+            .collect::<Vec<Vec<usize>>>();
+        let distances = shortest_distance_between_valves(&tunnels);
+
+        Ok(Valves {
+            valve_names: valve_names.into_iter().map(String::from).collect(),
+            flow_rates,
+            tunnels,
+            distances,
+        })
+    }
+
+    fn part1(valves: &Self::Input) -> Result<u32> {
+        valves.max_pressure(&Config::part1())
+    }
+
+    fn part2(valves: &Self::Input) -> Result<u32> {
+        valves.max_pressure(&Config::part2())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn example() {
+        let valves = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::part1(&valves).unwrap(), 1651);
+        assert_eq!(Day16::part2(&valves).unwrap(), 1707);
+    }
+
+    #[test]
+    fn bound_never_undershoots() {
+        let valves = Day16::parse(EXAMPLE).unwrap();
+        let start_idx = valves.valve_names.iter().position(|v| v == "AA").unwrap();
+        let network = ValveNetwork::new(&valves, start_idx);
+        let search = ValveSearch::new(&network);
+        assert_eq!(search.min_step, 2);
+        assert!(search.upper_bound(&[(network.start, 30)], 0) >= 1651);
+        assert!(search.upper_bound(&[(network.start, 26); 2], 0) >= 1707);
+        assert_eq!(search.upper_bound(&[(network.start, 30)], (1 << network.openable) - 1), 0);
+        let (released, routes) = search.max_release(30, 1);
+        assert_eq!(released, 1651);
+        assert_eq!(routes.len(), 1);
+    }
+
+    #[test]
+    fn schedule_of_example() {
+        let valves = Day16::parse(EXAMPLE).unwrap();
+        let schedule = valves.schedule(&Config::part1()).unwrap();
+        let opened = schedule.openings.iter().map(|opening| (opening.minute, opening.valve.as_str())).collect::<Vec<_>>();
+        assert_eq!(opened, [(2, "DD"), (5, "BB"), (9, "JJ"), (17, "HH"), (21, "EE"), (24, "CC")]);
+        assert_eq!(schedule.openings[1].path, ["DD", "CC", "BB"]);
+        let pressure = schedule.pressure_per_minute();
+        assert_eq!(pressure[2], (20, 20));
+        assert_eq!(pressure[29], (81, 1651));
+
+        let schedule = valves.schedule(&Config::part2()).unwrap();
+        assert_eq!(schedule.released, 1707);
+        assert_eq!(schedule.pressure_per_minute().last().unwrap().1, 1707);
+        let display = schedule.to_string();
+        assert!(display.starts_with("1707 pressure released in 26 minutes by 2 agents\n"));
+        // either agent may be the elephant
+        let jj = display.lines().find(|line| line.contains("opens JJ")).unwrap();
+        assert!(jj.starts_with("minute  3: agent "));
+        assert!(jj.ends_with(" opens JJ (flow rate 21) after walking AA -> II -> JJ"));
+        assert!(display.ends_with("    26         81      1707\n"));
+    }
+
+    #[test]
+    fn dot_exports() {
+        let valves = Day16::parse(EXAMPLE).unwrap();
+        let dot = valves.tunnels_dot(None);
+        assert!(dot.starts_with("graph tunnels {\n    AA [label=\"AA\\n0\"];\n"));
+        // every tunnel is listed in both directions in the input, but drawn once
+        assert_eq!(dot.matches(" -- ").count(), 10);
+        assert!(dot.contains("    GG -- HH;\n"));
+
+        let schedule = valves.schedule(&Config::part1()).unwrap();
+        let dot = valves.tunnels_dot(Some(&schedule));
+        assert!(dot.contains("    HH [label=\"HH\\n22\", color=red, penwidth=2];\n"));
+        assert!(dot.contains("    GG -- HH [color=red, penwidth=3];\n"));
+
+        let dot = valves.compressed_dot(Some("AA"), Some(&schedule)).unwrap();
+        // AA and the six valves worth opening, all connected
+        assert_eq!(dot.matches(" [label=\"").count(), 7);
+        assert_eq!(dot.matches(" -- ").count(), 21);
+        assert!(dot.contains("    BB -- JJ [label=3, color=red, penwidth=3];\n"));
+        assert!(dot.contains("    AA -- HH [label=5];\n"));
+        assert!(dot.contains("    HH -- JJ [label=7, color=red, penwidth=3];\n"));
+        assert_eq!(dot.matches("penwidth=3").count(), 6);
+        assert!(valves.compressed_dot(Some("ZZ"), None).is_err());
+    }
+
+    #[test]
+    fn more_agents_and_other_starts() {
+        let valves = Day16::parse(EXAMPLE).unwrap();
+        let config = |time_limit, start: &str, agents| Config { time_limit, start: Some(start.to_string()), agents };
+
+        assert!(valves.max_pressure(&config(30, "AA", 2)).unwrap() > 1707);
+        assert_eq!(valves.max_pressure(&config(0, "AA", 3)).unwrap(), 0);
+        // one valve each: every agent walks straight to its own
+        let alone = [("BB", 1), ("CC", 2), ("DD", 1), ("EE", 2), ("HH", 5), ("JJ", 2)];
+        let flow = |name| valves.flow_rates[valves.valve_names.iter().position(|v| v == name).unwrap()];
+        let expected = alone.iter().map(|&(name, distance)| flow(name) * (26 - distance - 1)).sum::<u32>();
+        assert_eq!(valves.max_pressure(&config(26, "AA", 6)).unwrap(), expected);
+        // starting next to JJ makes it cheaper to open
+        assert!(valves.max_pressure(&config(30, "II", 1)).unwrap() > 1651);
+
+        assert!(valves.max_pressure(&config(30, "ZZ", 1)).is_err());
+        assert!(valves.max_pressure(&config(30, "AA", 0)).is_err());
+    }
+
+    #[test]
+    fn starts_from_the_first_valve_without_aa() {
+        let valves = Day16::parse("\
+Valve XX has flow rate=0; tunnels lead to valves YY
+Valve YY has flow rate=10; tunnels lead to valves XX, ZZ
+Valve ZZ has flow rate=0; tunnels lead to valves YY
+")
+        .unwrap();
+        assert_eq!(Day16::part1(&valves).unwrap(), 10 * 28);
+        let from_zz = Config { start: Some("ZZ".to_string()), ..Config::part1() };
+        assert_eq!(valves.max_pressure(&from_zz).unwrap(), 10 * 28);
+        assert!(Day16::parse("").is_err());
+    }
+
+    #[test]
+    fn opens_the_start_valve_when_it_has_a_flow_rate() {
+        let valves = Day16::parse("\
+Valve AA has flow rate=5; tunnels lead to valves BB
+Valve BB has flow rate=0; tunnels lead to valves AA
+")
+        .unwrap();
+        assert_eq!(Day16::part1(&valves).unwrap(), 145);
+        let schedule = valves.schedule(&Config::part1()).unwrap();
+        assert_eq!(schedule.openings[0].minute, 1);
+        assert_eq!(schedule.openings[0].path, ["AA"]);
+        // opening it first is not always best
+        let valves = Day16::parse("\
+Valve AA has flow rate=1; tunnels lead to valves BB
+Valve BB has flow rate=50; tunnels lead to valves AA
+")
+        .unwrap();
+        assert_eq!(Day16::part1(&valves).unwrap(), 50 * 28 + 26);
+    }
+
+    #[test]
+    fn long_corridor_of_valves() {
+        // AA - V01 - V02 - ... - V30, all with the same flow rate: open them in order,
+        // one every two minutes
+        let mut input = "Valve AA has flow rate=0; tunnels lead to valves V01\n".to_string();
+        for i in 1..=30 {
+            let next = if i < 30 { format!(", V{:02}", i + 1) } else { String::new() };
+            input += &format!("Valve V{i:02} has flow rate=1; tunnels lead to valves V{:02}{next}\n", i - 1);
+        }
+        let input = input.replace("V00", "AA");
+        let valves = Day16::parse(&input).unwrap();
+        assert_eq!(Day16::part1(&valves).unwrap(), (1..=14).map(|k| 30 - 2 * k).sum());
+    }
+
+    #[test]
+    fn every_valve_next_to_every_other() {
+        // AA and V01 to V20 with flow rates 1 to 20, all one minute apart: every agent opens
+        // one valve every two minutes, the highest flow rates first
+        let names = (0..=20).map(|i| if i == 0 { "AA".to_string() } else { format!("V{i:02}") }).collect::<Vec<_>>();
+        let input = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let others = names.iter().filter(|&other| other != name).join(", ");
+                format!("Valve {name} has flow rate={i}; tunnels lead to valves {others}\n")
+            })
+            .collect::<String>();
+        let valves = Day16::parse(&input).unwrap();
+        let part1 = (1..=14).map(|k| (21 - k) * (30 - 2 * k)).sum::<u32>();
+        assert_eq!(Day16::part1(&valves).unwrap(), part1);
+        let part2 = (0..20).map(|i| (20 - i) * (24 - 2 * (i / 2))).sum::<u32>();
+        assert_eq!(Day16::part2(&valves).unwrap(), part2);
+    }
+
+    #[test]
+    fn pairing_opened_sets_agrees_with_searching_together() {
+        // a ring of 12 valves with a few shortcuts across it and made up flow rates
+        let mut seed = 16u32;
+        let mut random = |below: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) % below
+        };
+        let names = (0..12).map(|i| if i == 0 { "AA".to_string() } else { format!("V{i:02}") }).collect::<Vec<_>>();
+        let mut tunnels = (0..12).map(|i| vec![(i + 1) % 12, (i + 11) % 12]).collect::<Vec<_>>();
+        for _ in 0..4 {
+            let (a, b) = (random(12) as usize, random(12) as usize);
+            if a != b && !tunnels[a].contains(&b) {
+                tunnels[a].push(b);
+                tunnels[b].push(a);
+            }
+        }
+        let input = (0..12)
+            .map(|i| {
+                let flow_rate = if i == 0 || random(3) == 0 { 0 } else { random(25) + 1 };
+                let leads_to = tunnels[i].iter().map(|&j| &names[j]).join(", ");
+                format!("Valve {} has flow rate={flow_rate}; tunnels lead to valves {leads_to}\n", names[i])
+            })
+            .collect::<String>();
+        let valves = Day16::parse(&input).unwrap();
+        let network = ValveNetwork::new(&valves, 0);
+
+        let alone = best_pressure_per_opened_set(&network, 30);
+        assert_eq!(alone.into_values().map(|(released, _)| released).max(), Some(Day16::part1(&valves).unwrap()));
+        let (paired, routes) = pair_opened_sets(&network, 26);
+        assert_eq!(ValveSearch::new(&network).max_release(26, 2).0, paired);
+        assert_eq!(Day16::part2(&valves).unwrap(), paired);
+        // each valve is opened by one agent at most
+        let opened = routes.iter().flatten().map(|&(valve, _)| valve).collect::<Vec<_>>();
+        assert!(opened.iter().all_unique());
+        assert_eq!(routes.iter().flatten().map(|&(valve, time_left)| network.flow_rates[valve] * time_left).sum::<u32>(), paired);
+    }
+
+    #[test]
+    fn distances_between_valves() {
+        let valves = Day16::parse(EXAMPLE).unwrap();
+        let index = |name: &str| valves.valve_names.iter().position(|v| v == name).unwrap();
+        assert_eq!(valves.distances[index("AA")][index("HH")], 5);
+        assert_eq!(valves.distances[index("JJ")][index("HH")], 7);
+    }
+
+    #[test]
+    fn reports_unknown_valves() {
+        let err = Day16::parse("Valve AA has flow rate=0; tunnel leads to valve ZZ\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 49: tunnel leads to unknown valve `ZZ`");
+    }
+}
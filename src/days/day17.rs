@@ -0,0 +1,584 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+/// The puzzle's rocks in the order they fall, in the format read by [`parse_shapes`].
+pub const ROCKS: &str = "\
+####
+
+.#.
+###
+.#.
+
+..#
+..#
+###
+
+#
+#
+#
+#
+
+##
+##
+";
+
+/// A rock as one bitmask per row, bottom row first, where bit x is set when the rock has a cell
+/// x units right of the left edge of its bounding box.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    rows: Vec<u8>,
+    width: u32,
+}
+
+impl Shape {
+    // cells are (x, y) with y pointing up, x below 8
+    fn new(cells: Vec<(u32, u32)>) -> Self {
+        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or_default();
+        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or_default();
+        let max_y = cells.iter().map(|&(_, y)| y).max().unwrap_or_default();
+        let mut rows = vec![0u8; (max_y - min_y + 1) as usize];
+        for (x, y) in cells {
+            rows[(y - min_y) as usize] |= 1 << (x - min_x);
+        }
+        let width = rows.iter().map(|row| u8::BITS - row.leading_zeros()).max().unwrap_or_default();
+        Self { rows, width }
+    }
+}
+
+/// Parses rocks drawn with `#` for rock and `.` for air, top row first, separated by blank lines.
+pub fn parse_shapes(text: &str) -> Result<Vec<Shape>> {
+    parse::blocks(text)
+        .map(|block| {
+            let rows = block.lines().collect::<Vec<_>>();
+            let mut cells = vec![];
+            for (row, line) in rows.iter().enumerate() {
+                for (col, c) in line.char_indices() {
+                    match c {
+                        '#' if col >= u8::BITS as usize => {
+                            return Err(Error::at(text, &line[col..], format!("rocks are at most {} units wide", u8::BITS)))
+                        }
+                        '#' => cells.push((col as u32, (rows.len() - 1 - row) as u32)),
+                        '.' => {}
+                        _ => return Err(Error::at(text, &line[col..], format!("unknown cell `{c}`, expected `#` or `.`"))),
+                    }
+                }
+            }
+            if cells.is_empty() {
+                return Err(Error::at(text, block, "a rock needs at least one `#`"));
+            }
+            Ok(Shape::new(cells))
+        })
+        .collect()
+}
+
+/// Which rocks fall, in order, into a chamber how many units wide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<Shape>,
+    width: usize,
+}
+
+impl Rules {
+    /// Fails unless there is at least one rock, every rock fits two units from the left wall
+    /// and the chamber is at most 8 units wide.
+    pub fn new(shapes: Vec<Shape>, width: usize) -> Result<Self> {
+        if shapes.is_empty() {
+            return Err(Error::Invalid("at least one rock has to fall".to_string()));
+        }
+        if width > u8::BITS as usize {
+            return Err(Error::Invalid(format!("chambers are at most {} units wide", u8::BITS)));
+        }
+        if let Some(shape) = shapes.iter().find(|shape| 2 + shape.width as usize > width) {
+            return Err(Error::Invalid(format!(
+                "a rock {} units wide does not fit in a chamber {width} units wide",
+                shape.width
+            )));
+        }
+        Ok(Self { shapes, width })
+    }
+
+    /// The puzzle's five rocks in a chamber seven units wide.
+    pub fn puzzle() -> Self {
+        Self::new(parse_shapes(ROCKS).unwrap(), 7).unwrap()
+    }
+}
+
+// settled rock, one bitmask per row with bit x set for rock x units from the left wall;
+// rows no falling rock can reach any more are dropped from the bottom
+struct Chamber {
+    rows: Vec<u8>,
+    // absolute height of rows[0]
+    pruned: usize,
+    height: usize,
+    // every column of a row
+    full: u8,
+    // rows held before trying to prune again, twice as many as were left by the last try so
+    // that a tower whose rows all stay in reach is not rescanned for every rock
+    prune_at: usize,
+}
+
+// rows held before trying to prune the ones out of reach for the first time
+const MAX_ROWS: usize = 256;
+
+impl Chamber {
+    fn new(width: usize) -> Self {
+        let full = (1u16 << width).wrapping_sub(1) as u8;
+        // bottom row
+        Self { rows: vec![full], pruned: 0, height: 0, full, prune_at: MAX_ROWS }
+    }
+
+    // rock at absolute height y, pruned rows count as rock
+    fn row(&self, y: usize) -> u8 {
+        match y.checked_sub(self.pruned) {
+            Some(i) => self.rows.get(i).copied().unwrap_or(0),
+            None => self.full,
+        }
+    }
+
+    // whether `shape` with its bottom-left corner at (x, y) overlaps neither walls nor rock
+    fn fits(&self, shape: &Shape, (x, y): (i32, i32)) -> bool {
+        if x < 0 || y < 0 || (x as u32 + shape.width) > self.full.count_ones() {
+            return false;
+        }
+        shape
+            .rows
+            .iter()
+            .enumerate()
+            .all(|(dy, &row)| self.row(y as usize + dy) & (row << x) == 0)
+    }
+
+    fn settle(&mut self, shape: &Shape, (x, y): (i32, i32)) {
+        for (dy, &row) in shape.rows.iter().enumerate() {
+            let i = y as usize + dy - self.pruned;
+            if self.rows.len() <= i {
+                self.rows.resize(i + 1, 0);
+            }
+            self.rows[i] |= row << x;
+        }
+        self.height = self.height.max(y as usize + shape.rows.len() - 1);
+        if self.rows.len() > self.prune_at {
+            self.prune();
+        }
+    }
+
+    // the lowest row that air from above the tower can still reach moving down, left or
+    // right, less one for rocks to land on, looking no lower than `bottom`; nothing below
+    // the floor from the very bottom can matter any more
+    fn floor(&self, bottom: usize) -> usize {
+        let bottom = bottom.max(self.pruned);
+        let mut reachable = self.full;
+        let mut lowest = self.height + 1;
+        for y in (bottom..=self.height).rev() {
+            let free = !self.row(y) & self.full;
+            let mut spread = reachable & free;
+            loop {
+                let wider = (spread | spread << 1 | spread >> 1) & free;
+                if wider == spread {
+                    break;
+                }
+                spread = wider;
+            }
+            if spread == 0 {
+                break;
+            }
+            (reachable, lowest) = (spread, y);
+        }
+        (lowest - 1).max(bottom)
+    }
+
+    // drops the rows below the floor
+    fn prune(&mut self) {
+        let keep_from = self.floor(0);
+        self.rows.drain(..keep_from - self.pruned);
+        self.pruned = keep_from;
+        self.prune_at = MAX_ROWS.max(2 * self.rows.len());
+    }
+
+    // the top `depth` rows from the floor up
+    fn surface(&self, depth: usize) -> Vec<u8> {
+        (self.floor((self.height + 1).saturating_sub(depth))..=self.height).map(|y| self.row(y)).collect()
+    }
+}
+
+/// A game in progress, advanced one jet push or one fall at a time so it can be watched.
+pub struct Tower<'a> {
+    jet_pattern: &'a [i32],
+    rules: &'a Rules,
+    chamber: Chamber,
+    i_jet: usize,
+    // rocks that came to rest
+    rocks: u64,
+    // the row under the last rock to come to rest, the lowest one it had to look at
+    landed_on: usize,
+    falling: Option<Falling>,
+}
+
+// bottom-left corner of the falling rock and what happens to it next
+#[derive(Debug, Clone, Copy)]
+enum Falling {
+    Push((i32, i32)),
+    Fall((i32, i32)),
+}
+
+impl<'a> Tower<'a> {
+    pub fn new(jet_pattern: &'a [i32], rules: &'a Rules) -> Self {
+        Self { jet_pattern, rules, chamber: Chamber::new(rules.width), i_jet: 0, rocks: 0, landed_on: 0, falling: None }
+    }
+
+    pub fn height(&self) -> u64 {
+        self.chamber.height as u64
+    }
+
+    /// Rocks that came to rest so far.
+    pub fn rocks(&self) -> u64 {
+        self.rocks
+    }
+
+    fn shape(&self) -> &'a Shape {
+        let rules = self.rules;
+        &rules.shapes[(self.rocks % rules.shapes.len() as u64) as usize]
+    }
+
+    /// Makes the next rock appear, or pushes the falling rock with the next jet, or lets it
+    /// fall one unit, in the order of the puzzle's pictures. Returns whether a rock came to rest.
+    pub fn step(&mut self) -> bool {
+        let shape = self.shape();
+        match self.falling {
+            None => {
+                // appears two units from the left wall and three above the highest rock
+                self.falling = Some(Falling::Push((2, self.chamber.height as i32 + 4)));
+                false
+            }
+            Some(Falling::Push(position)) => {
+                let pushed = (position.0 + self.jet_pattern[self.i_jet], position.1);
+                self.i_jet = (self.i_jet + 1) % self.jet_pattern.len();
+                let position = if self.chamber.fits(shape, pushed) { pushed } else { position };
+                self.falling = Some(Falling::Fall(position));
+                false
+            }
+            Some(Falling::Fall(position)) => {
+                let fallen = (position.0, position.1 - 1);
+                if self.chamber.fits(shape, fallen) {
+                    self.falling = Some(Falling::Push(fallen));
+                    return false;
+                }
+                self.chamber.settle(shape, position);
+                self.rocks += 1;
+                self.landed_on = fallen.1 as usize;
+                self.falling = None;
+                true
+            }
+        }
+    }
+
+    /// Steps until the falling rock, or the next one, comes to rest.
+    pub fn drop_rock(&mut self) {
+        while !self.step() {}
+    }
+
+    /// The top `rows` rows of the chamber drawn like the puzzle does, `#` for rock at rest and
+    /// `@` for the falling rock, with the floor below once it is in view, or how many rows were
+    /// pruned once the lowest row still held is.
+    pub fn render(&self, rows: usize) -> String {
+        let shape = self.shape();
+        let falling = self.falling.map(|(Falling::Push(position) | Falling::Fall(position))| position);
+        let top = match falling {
+            Some((_, y)) => y as usize + shape.rows.len() - 1,
+            None => self.chamber.height,
+        };
+        // the floor counts as pruned with the rows above it
+        let held = self.chamber.pruned.max(1);
+        let bottom = (top + 1).saturating_sub(rows).max(held);
+        let mut text = String::new();
+        for y in (bottom..=top).rev() {
+            let rock = self.chamber.row(y);
+            let moving = falling
+                .and_then(|(x, fy)| Some(shape.rows.get(y.checked_sub(fy as usize)?)? << x))
+                .unwrap_or(0);
+            text.push('|');
+            text.extend((0..self.rules.width).map(|x| match (moving >> x & 1, rock >> x & 1) {
+                (1, _) => '@',
+                (_, 1) => '#',
+                _ => '.',
+            }));
+            text.push_str("|\n");
+        }
+        if (top + 1).saturating_sub(rows) < held {
+            let edge = if held == 1 { '-' } else { '~' };
+            text.push('+');
+            text.extend((0..self.rules.width).map(|_| edge));
+            text.push('+');
+            if held > 1 {
+                write!(text, " {} rows pruned", held - 1).unwrap();
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// The chamber after `rocks` rocks came to rest, down to the lowest row still held, as drawn
+/// by [`Tower::render`].
+pub fn snapshot(jet_pattern: &[i32], rules: &Rules, rocks: u64) -> String {
+    let mut tower = Tower::new(jet_pattern, rules);
+    while tower.rocks() < rocks {
+        tower.drop_rock();
+    }
+    tower.render(tower.chamber.height + 1)
+}
+
+fn tetris_game(jet_pattern: &[i32], rules: &Rules, game_duration: u64) -> u64 {
+    let mut tower = Tower::new(jet_pattern, rules);
+    // rocks dropped and height reached the first time each (rock, jet, surface) state was seen
+    let mut seen = HashMap::new();
+    let mut skipped_height = None;
+    // rocks still to drop once whole cycles are skipped
+    let mut to_drop = game_duration;
+    // the row each rock landed on, and the most rows below the top any rock looked at,
+    // which is how much of the surface goes in the state
+    let mut landed_on = Vec::new();
+    let mut depth = 1;
+    while tower.rocks < to_drop {
+        if skipped_height.is_none() {
+            let i_shape = (tower.rocks % rules.shapes.len() as u64) as usize;
+            let surface = tower.chamber.surface(depth);
+            let bottom = tower.chamber.height + 1 - surface.len();
+            let state = (i_shape, tower.i_jet, surface);
+            if let Some((earlier_rocks, earlier_height, earlier_bottom)) =
+                seen.insert(state, (tower.rocks, tower.height(), bottom))
+            {
+                // when no rock since looked below the surface then, the same rocks will land
+                // the same way on the same surface again and again
+                if landed_on[earlier_rocks as usize..].iter().all(|&y| y >= earlier_bottom) {
+                    let cycle_len = tower.rocks - earlier_rocks;
+                    let cycles = (game_duration - tower.rocks) / cycle_len;
+                    skipped_height = Some(cycles * (tower.height() - earlier_height));
+                    to_drop -= cycles * cycle_len;
+                    continue;
+                }
+            }
+        }
+        let height = tower.chamber.height;
+        tower.drop_rock();
+        landed_on.push(tower.landed_on);
+        depth = depth.max(height + 1 - tower.landed_on);
+    }
+    tower.height() + skipped_height.unwrap_or_default()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    // +1 pushes right, -1 pushes left
+    type Input = Vec<i32>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let jet_pattern = input.trim_end();
+        if jet_pattern.is_empty() {
+            return Err(Error::Invalid("the jet pattern is empty".to_string()));
+        }
+        jet_pattern
+            .char_indices()
+            .map(|(i, c)| match c {
+                '>' => Ok(1),
+                '<' => Ok(-1),
+                _ => Err(Error::at(input, &jet_pattern[i..], format!("unknown jet `{c}`, expected `<` or `>`")))
+            })
+            .collect::<Result<Vec<i32>>>()
+    }
+
+    fn part1(jet_pattern: &Self::Input) -> Result<u64> {
+        Ok(tetris_game(jet_pattern, &Rules::puzzle(), 2022))
+    }
+
+    fn part2(jet_pattern: &Self::Input) -> Result<u64> {
+        Ok(tetris_game(jet_pattern, &Rules::puzzle(), 1_000_000_000_000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+
+    #[test]
+    fn example() {
+        let jet_pattern = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part1(&jet_pattern).unwrap(), 3068);
+        assert_eq!(Day17::part2(&jet_pattern).unwrap(), 1514285714288);
+    }
+
+    #[test]
+    fn heights_of_first_rocks() {
+        // from the puzzle's pictures, before any repeating pattern is found
+        let jet_pattern = Day17::parse(EXAMPLE).unwrap();
+        let heights = (0..=10).map(|rocks| tetris_game(&jet_pattern, &Rules::puzzle(), rocks)).collect::<Vec<_>>();
+        assert_eq!(heights, [0, 1, 4, 6, 7, 9, 10, 13, 15, 17, 17]);
+    }
+
+    #[test]
+    fn renders_the_puzzle_pictures() {
+        let jet_pattern = Day17::parse(EXAMPLE).unwrap();
+        let rules = Rules::puzzle();
+        let mut tower = Tower::new(&jet_pattern, &rules);
+        tower.step();
+        assert_eq!(tower.render(10), "|..@@@@.|\n|.......|\n|.......|\n|.......|\n+-------+\n");
+        tower.step();
+        assert_eq!(tower.render(10), "|...@@@@|\n|.......|\n|.......|\n|.......|\n+-------+\n");
+        tower.drop_rock();
+        tower.step();
+        let second_rock = "\
+|...@...|
+|..@@@..|
+|...@...|
+|.......|
+|.......|
+|.......|
+|..####.|
++-------+
+";
+        assert_eq!(tower.render(10), second_rock);
+        // only the top rows
+        assert_eq!(tower.render(2), "|...@...|\n|..@@@..|\n");
+
+        let ten_rocks = "\
+|....#..|
+|....#..|
+|....##.|
+|##..##.|
+|######.|
+|.###...|
+|..#....|
+|.####..|
+|....##.|
+|....##.|
+|....#..|
+|..#.#..|
+|..#.#..|
+|#####..|
+|..###..|
+|...#...|
+|..####.|
++-------+
+";
+        assert_eq!(snapshot(&jet_pattern, &rules, 10), ten_rocks);
+        assert_eq!(snapshot(&jet_pattern, &rules, 0), "+-------+\n");
+
+        // rows out of reach are left out rather than drawn as rock
+        let mut tower = Tower::new(&jet_pattern, &rules);
+        while tower.rocks() < 2022 {
+            tower.drop_rock();
+        }
+        let pruned = tower.chamber.pruned - 1;
+        let held = tower.chamber.height - pruned;
+        assert!(pruned > 0);
+        let picture = snapshot(&jet_pattern, &rules, 2022);
+        assert_eq!(picture.lines().count(), held + 1);
+        assert!(picture.ends_with(&format!("|\n+~~~~~~~+ {pruned} rows pruned\n")));
+        assert_eq!(tower.render(held + 10), picture);
+        assert_eq!(tower.render(held).lines().count(), held);
+    }
+
+    #[test]
+    fn deep_wells_are_part_of_the_repeating_state() {
+        // leaves a well two units wide and more than 64 rows deep next to the tower
+        let jet_pattern = Day17::parse(">>><<>><><><><>").unwrap();
+        let rules = Rules::puzzle();
+        let mut tower = Tower::new(&jet_pattern, &rules);
+        while tower.rocks() < 2022 {
+            tower.drop_rock();
+        }
+        assert_eq!(tower.height(), 3473);
+        assert_eq!(Day17::part1(&jet_pattern).unwrap(), 3473);
+        // skipping whole cycles lands where dropping every rock does
+        while tower.rocks() < 20_000 {
+            tower.drop_rock();
+        }
+        assert_eq!(tetris_game(&jet_pattern, &rules, 20_000), tower.height());
+        // the well stays open to the floor, yet rocks stop looking into it
+        assert_eq!(Day17::part2(&jet_pattern).unwrap(), 1_719_999_999_995);
+    }
+
+    #[test]
+    fn shapes_from_text() {
+        let shapes = parse_shapes(ROCKS).unwrap();
+        assert_eq!(shapes.len(), 5);
+        assert_eq!(shapes[1].rows, [0b010, 0b111, 0b010]);
+        assert_eq!(shapes[2].rows, [0b111, 0b100, 0b100]);
+        assert_eq!((shapes[2].width, shapes[3].width), (3, 1));
+
+        let err = parse_shapes("##\n\n#x\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 2: unknown cell `x`, expected `#` or `.`");
+        assert!(parse_shapes("##\n\n..\n").is_err());
+    }
+
+    #[test]
+    fn custom_rocks_and_widths() {
+        let jet_pattern = Day17::parse(EXAMPLE).unwrap();
+        // any two bars three units wide overlap in the middle of a chamber five units wide,
+        // so each one lands right on top of the previous one whatever the jets do
+        let bars = Rules::new(parse_shapes("###").unwrap(), 5).unwrap();
+        assert_eq!(tetris_game(&jet_pattern, &bars, 1000), 1000);
+        // a square and a pillar that take turns in a chamber as narrow as they allow
+        let rules = Rules::new(parse_shapes("##\n##\n\n#\n#\n").unwrap(), 4).unwrap();
+        assert_eq!(tetris_game(&jet_pattern, &rules, 0), 0);
+        assert_eq!(tetris_game(&jet_pattern, &rules, 1), 2);
+
+        assert!(Rules::new(parse_shapes("####").unwrap(), 5).is_err());
+        assert!(Rules::new(vec![], 7).is_err());
+        assert!(Rules::new(parse_shapes("#").unwrap(), 9).is_err());
+    }
+
+    #[test]
+    fn prunes_rows_out_of_reach() {
+        let bar = &parse_shapes("####").unwrap()[0];
+        let mut chamber = Chamber::new(4);
+        for y in 1..=10_000 {
+            chamber.settle(bar, (0, y));
+        }
+        assert_eq!(chamber.height, 10_000);
+        assert!(chamber.rows.len() <= MAX_ROWS);
+        assert!(!chamber.fits(bar, (0, 10_000)));
+        assert!(chamber.fits(bar, (0, 10_001)));
+
+        // a gap air can still reach keeps the rows around it
+        let mut chamber = Chamber::new(4);
+        let dot = &parse_shapes("#").unwrap()[0];
+        for y in 1..=300 {
+            chamber.settle(dot, (0, y));
+        }
+        chamber.prune();
+        assert_eq!(chamber.pruned, 0);
+    }
+
+    #[test]
+    fn towers_that_never_prune_stay_linear() {
+        // pushed against the right wall, no rock ever fills the three columns on the left, so
+        // every row stays in reach; rescanning them all for each rock would take minutes here
+        let jet_pattern = Day17::parse(">").unwrap();
+        let rules = Rules::puzzle();
+        let mut tower = Tower::new(&jet_pattern, &rules);
+        while tower.rocks() < 100_000 {
+            tower.drop_rock();
+        }
+        let chamber = &tower.chamber;
+        assert_eq!(chamber.pruned, 0);
+        assert_eq!(chamber.rows.len(), chamber.height + 1);
+        assert!(chamber.prune_at < 4 * chamber.rows.len());
+    }
+
+    #[test]
+    fn reports_unknown_jets() {
+        let err = Day17::parse(">><^<").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 4: unknown jet `^`, expected `<` or `>`");
+    }
+}
@@ -0,0 +1,629 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::iter;
+
+use itertools::{iproduct, Either, Itertools};
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::search;
+use crate::solution::Solution;
+
+/// A unit cube, named by the coordinates of its corner nearest the origin.
+pub type Voxel = (i32, i32, i32);
+
+fn parse_line(input: &str, s: &str) -> Result<Voxel> {
+    let values = s
+        .split(',')
+        .map(|value| parse::number::<i32>(input, value))
+        .collect::<Result<Vec<i32>>>()?;
+
+    match values[..] {
+        [x, y, z] => Ok((x, y, z)),
+        _ => Err(Error::at(input, s, format!("expected `x,y,z`, found {} values", values.len()))),
+    }
+}
+
+const DIRECTIONS: [Voxel; 6] = [(-1, 0, 0), (1, 0, 0), (0, -1, 0), (0, 1, 0), (0, 0, -1), (0, 0, 1)];
+
+fn neighbours((x, y, z): Voxel) -> impl Iterator<Item = Voxel> {
+    DIRECTIONS.into_iter().map(move |(dx, dy, dz)| (x + dx, y + dy, z + dz))
+}
+
+/// How [`Voxels`] stores its cubes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// A hash set, for cubes scattered over a large space; searches for air stay next to the
+    /// cubes instead of covering their bounding box.
+    #[default]
+    Sparse,
+    /// One bit per cell of the bounding box, for cubes packed close together.
+    Dense,
+}
+
+impl Backend {
+    /// [`Backend::Dense`] unless the bounding box of `cubes` has more than 64 cells per cube.
+    pub fn suited_to(cubes: &[Voxel]) -> Self {
+        match bounds(cubes.iter().copied()) {
+            Some((min, max)) if BitVolume::cells(min, max) <= cubes.len().saturating_mul(64) => Backend::Dense,
+            _ => Backend::Sparse,
+        }
+    }
+}
+
+/// A set of unit cubes, such as a lava droplet.
+#[derive(Debug, Clone, Default)]
+pub struct Voxels {
+    storage: Storage,
+}
+
+impl FromIterator<Voxel> for Voxels {
+    fn from_iter<I: IntoIterator<Item = Voxel>>(cubes: I) -> Self {
+        Self::new(cubes, Backend::Sparse)
+    }
+}
+
+// the same cubes, whichever way they are stored
+impl PartialEq for Voxels {
+    fn eq(&self, other: &Self) -> bool {
+        self.volume() == other.volume() && self.iter().all(|voxel| other.contains(voxel))
+    }
+}
+
+impl Eq for Voxels {}
+
+impl Voxels {
+    pub fn new(cubes: impl IntoIterator<Item = Voxel>, backend: Backend) -> Self {
+        let storage = match backend {
+            Backend::Sparse => Storage::Sparse(cubes.into_iter().collect()),
+            Backend::Dense => {
+                let cubes = cubes.into_iter().collect::<Vec<_>>();
+                let (min, max) = bounds(cubes.iter().copied()).unwrap_or(((0, 0, 0), (-1, -1, -1)));
+                let mut volume = BitVolume::new(min, max);
+                for cube in cubes {
+                    volume.insert(cube);
+                }
+                Storage::Dense(volume)
+            }
+        };
+        Self { storage }
+    }
+
+    pub fn backend(&self) -> Backend {
+        match self.storage {
+            Storage::Sparse(_) => Backend::Sparse,
+            Storage::Dense(_) => Backend::Dense,
+        }
+    }
+
+    pub fn contains(&self, voxel: Voxel) -> bool {
+        self.storage.contains(voxel)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Voxel> + '_ {
+        self.storage.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.volume() == 0
+    }
+
+    /// Number of cubes.
+    pub fn volume(&self) -> usize {
+        match &self.storage {
+            Storage::Sparse(cubes) => cubes.len(),
+            Storage::Dense(volume) => volume.len,
+        }
+    }
+
+    /// Lowest and highest cube along each axis, or `None` when there are no cubes.
+    pub fn bounds(&self) -> Option<(Voxel, Voxel)> {
+        bounds(self.storage.iter())
+    }
+
+    /// Faces not shared by two cubes, including those around cavities.
+    pub fn surface_area(&self) -> usize {
+        self.iter().map(|voxel| neighbours(voxel).filter(|&neighbour| !self.contains(neighbour)).count()).sum()
+    }
+
+    /// Faces that air from outside can reach, leaving out those around cavities.
+    pub fn exterior_surface_area(&self) -> usize {
+        let outside = self.outside_air();
+        self.iter().map(|voxel| neighbours(voxel).filter(|&neighbour| outside.contains(neighbour)).count()).sum()
+    }
+
+    /// Groups of cubes joined face to face, ordered by their lowest cube.
+    pub fn components(&self) -> Vec<Voxels> {
+        let mut seen = HashSet::new();
+        let mut components = vec![];
+        for cube in self.iter().sorted() {
+            if seen.contains(&cube) {
+                continue;
+            }
+            let component = search::reachable(cube, |&voxel| {
+                neighbours(voxel).filter(|&neighbour| self.contains(neighbour)).collect::<Vec<_>>()
+            });
+            seen.extend(component.iter().copied());
+            components.push(Voxels::new(component, self.backend()));
+        }
+        components
+    }
+
+    /// Pockets of air the cubes enclose completely, ordered by their lowest cube; the volume
+    /// of each is its size.
+    pub fn cavities(&self) -> Vec<Voxels> {
+        let Some(((min_x, min_y, min_z), (max_x, max_y, max_z))) = self.bounds() else {
+            return vec![];
+        };
+        match self.storage {
+            Storage::Sparse(_) => {
+                let enclosed = self.sorted_air().into_iter().filter(|&(_, outside)| !outside).map(|(voxel, _)| voxel);
+                Voxels::new(enclosed, Backend::Sparse).components()
+            }
+            Storage::Dense(_) => {
+                let outside = self.outside_air();
+                let enclosed = iproduct!(min_x..=max_x, min_y..=max_y, min_z..=max_z)
+                    .filter(|&voxel| !self.contains(voxel) && !outside.contains(voxel));
+                Voxels::new(enclosed, Backend::Dense).components()
+            }
+        }
+    }
+
+    /// Faces that air from outside can reach, in a stable order.
+    pub fn exterior_faces(&self) -> Vec<Face> {
+        let outside = self.outside_air();
+        self.iter()
+            .sorted()
+            .flat_map(|voxel| DIRECTIONS.into_iter().map(move |normal| Face { voxel, normal }))
+            .filter(|face| outside.contains(face.neighbour()))
+            .collect()
+    }
+
+    /// The exterior surface as an ASCII STL solid, two triangles per face.
+    pub fn to_stl(&self, name: &str) -> String {
+        let mut stl = format!("solid {name}\n");
+        for face in self.exterior_faces() {
+            let [a, b, c, d] = face.corners();
+            for triangle in [[a, b, c], [a, c, d]] {
+                let (nx, ny, nz) = face.normal;
+                writeln!(stl, "  facet normal {nx} {ny} {nz}\n    outer loop").unwrap();
+                for (x, y, z) in triangle {
+                    writeln!(stl, "      vertex {x} {y} {z}").unwrap();
+                }
+                stl.push_str("    endloop\n  endfacet\n");
+            }
+        }
+        writeln!(stl, "endsolid {name}").unwrap();
+        stl
+    }
+
+    /// The exterior surface as a Wavefront OBJ mesh of quads sharing their corners.
+    pub fn to_obj(&self) -> String {
+        let mut vertices = HashMap::new();
+        let mut obj = String::new();
+        let mut faces = String::new();
+        for face in self.exterior_faces() {
+            faces.push('f');
+            for corner in face.corners() {
+                let next = vertices.len() + 1;
+                let index = *vertices.entry(corner).or_insert_with(|| {
+                    let (x, y, z) = corner;
+                    writeln!(obj, "v {x} {y} {z}").unwrap();
+                    next
+                });
+                write!(faces, " {index}").unwrap();
+            }
+            faces.push('\n');
+        }
+        obj + &faces
+    }
+
+    // air connected to the outside: for the dense backend all of it within one unit of the
+    // bounding box, for the sparse one only the cells next to a cube
+    fn outside_air(&self) -> Storage {
+        let Some((min, max)) = self.bounds() else {
+            return Storage::Sparse(HashSet::new());
+        };
+        if let Storage::Sparse(_) = self.storage {
+            let outside = self.sorted_air().into_iter().filter(|&(_, outside)| outside).map(|(voxel, _)| voxel);
+            return Storage::Sparse(outside.collect());
+        }
+        let (low, high) = ((min.0 - 1, min.1 - 1, min.2 - 1), (max.0 + 1, max.1 + 1, max.2 + 1));
+        let open = |&(x, y, z): &Voxel| {
+            (low.0..=high.0).contains(&x)
+                && (low.1..=high.1).contains(&y)
+                && (low.2..=high.2).contains(&z)
+                && !self.contains((x, y, z))
+        };
+        let mut air = BitVolume::new(low, high);
+        air.insert(low);
+        let mut stack = vec![low];
+        while let Some(voxel) = stack.pop() {
+            for neighbour in neighbours(voxel).filter(open) {
+                if air.insert(neighbour) {
+                    stack.push(neighbour);
+                }
+            }
+        }
+        Storage::Dense(air)
+    }
+
+    // whether air from outside reaches each air cell next to a cube, and each cell the fills
+    // from them passed through; a fill stops at the first cell with a clear line out along an
+    // axis, or one already sorted, so only cavities are filled completely and the cost follows
+    // the cubes rather than the space they are spread over
+    fn sorted_air(&self) -> HashMap<Voxel, bool> {
+        // lowest and highest cube on each line along each axis, keyed by the other coordinates
+        let mut lines: [HashMap<(i32, i32), (i32, i32)>; 3] = Default::default();
+        for (x, y, z) in self.iter() {
+            for (line, key, v) in [(0, (y, z), x), (1, (x, z), y), (2, (x, y), z)] {
+                let (low, high) = lines[line].entry(key).or_insert((v, v));
+                *low = (*low).min(v);
+                *high = (*high).max(v);
+            }
+        }
+        let in_sight = |(x, y, z): Voxel| {
+            [((y, z), x), ((x, z), y), ((x, y), z)]
+                .into_iter()
+                .zip(&lines)
+                .any(|((key, v), line)| line.get(&key).is_none_or(|&(low, high)| v < low || high < v))
+        };
+
+        let mut sorted = HashMap::new();
+        for start in self.iter().flat_map(neighbours).filter(|&voxel| !self.contains(voxel)) {
+            if sorted.contains_key(&start) {
+                continue;
+            }
+            // every cell seen is in the same pocket of air as `start`
+            let mut seen = HashSet::from([start]);
+            let mut stack = vec![start];
+            let mut outside = false;
+            while let Some(voxel) = stack.pop() {
+                if in_sight(voxel) {
+                    outside = true;
+                    break;
+                }
+                if let Some(&known) = sorted.get(&voxel) {
+                    outside = known;
+                    break;
+                }
+                for neighbour in neighbours(voxel).filter(|&neighbour| !self.contains(neighbour)) {
+                    if seen.insert(neighbour) {
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            sorted.extend(seen.into_iter().map(|voxel| (voxel, outside)));
+        }
+        sorted
+    }
+}
+
+fn bounds(cubes: impl Iterator<Item = Voxel> + Clone) -> Option<(Voxel, Voxel)> {
+    let (min_x, max_x) = cubes.clone().map(|(x, _, _)| x).minmax().into_option()?;
+    let (min_y, max_y) = cubes.clone().map(|(_, y, _)| y).minmax().into_option()?;
+    let (min_z, max_z) = cubes.map(|(_, _, z)| z).minmax().into_option()?;
+    Some(((min_x, min_y, min_z), (max_x, max_y, max_z)))
+}
+
+#[derive(Debug, Clone)]
+enum Storage {
+    Sparse(HashSet<Voxel>),
+    Dense(BitVolume),
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Storage::Sparse(HashSet::new())
+    }
+}
+
+impl Storage {
+    fn contains(&self, voxel: Voxel) -> bool {
+        match self {
+            Storage::Sparse(cubes) => cubes.contains(&voxel),
+            Storage::Dense(volume) => volume.contains(voxel),
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Voxel> + Clone + '_ {
+        match self {
+            Storage::Sparse(cubes) => Either::Left(cubes.iter().copied()),
+            Storage::Dense(volume) => Either::Right(volume.iter()),
+        }
+    }
+}
+
+// one bit per cell of a box starting at `min`, x major and z minor, so that cells are found by
+// arithmetic instead of hashing and come out in sorted order
+#[derive(Debug, Clone)]
+struct BitVolume {
+    min: Voxel,
+    size: (usize, usize, usize),
+    bits: Vec<u64>,
+    // cells set
+    len: usize,
+}
+
+impl BitVolume {
+    // sides of the box from `min` to `max`, both included
+    fn size(min: Voxel, max: Voxel) -> (usize, usize, usize) {
+        let side = |low: i32, high: i32| (i64::from(high) - i64::from(low) + 1).max(0) as usize;
+        (side(min.0, max.0), side(min.1, max.1), side(min.2, max.2))
+    }
+
+    fn cells(min: Voxel, max: Voxel) -> usize {
+        let (x, y, z) = Self::size(min, max);
+        x.saturating_mul(y).saturating_mul(z)
+    }
+
+    fn new(min: Voxel, max: Voxel) -> Self {
+        let bits = vec![0; Self::cells(min, max).div_ceil(64)];
+        Self { min, size: Self::size(min, max), bits, len: 0 }
+    }
+
+    fn index(&self, (x, y, z): Voxel) -> Option<usize> {
+        let offset = |v: i32, min: i32, size: usize| {
+            usize::try_from(i64::from(v) - i64::from(min)).ok().filter(|&offset| offset < size)
+        };
+        let dx = offset(x, self.min.0, self.size.0)?;
+        let dy = offset(y, self.min.1, self.size.1)?;
+        let dz = offset(z, self.min.2, self.size.2)?;
+        Some((dx * self.size.1 + dy) * self.size.2 + dz)
+    }
+
+    fn voxel(&self, index: usize) -> Voxel {
+        let (rest, dz) = (index / self.size.2, index % self.size.2);
+        let (dx, dy) = (rest / self.size.1, rest % self.size.1);
+        (self.min.0 + dx as i32, self.min.1 + dy as i32, self.min.2 + dz as i32)
+    }
+
+    fn contains(&self, voxel: Voxel) -> bool {
+        self.index(voxel).is_some_and(|i| self.bits[i / 64] >> (i % 64) & 1 == 1)
+    }
+
+    // whether the cell was not set before; panics outside the box
+    fn insert(&mut self, voxel: Voxel) -> bool {
+        let i = self.index(voxel).unwrap_or_else(|| panic!("{voxel:?} is outside the box"));
+        let (word, bit) = (&mut self.bits[i / 64], 1 << (i % 64));
+        let added = *word & bit == 0;
+        *word |= bit;
+        self.len += usize::from(added);
+        added
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Voxel> + Clone + '_ {
+        self.bits.iter().enumerate().flat_map(move |(w, &word)| {
+            let mut word = word;
+            iter::from_fn(move || {
+                let bit = word.trailing_zeros() as usize;
+                word &= word.wrapping_sub(1);
+                (bit < 64).then(|| self.voxel(w * 64 + bit))
+            })
+        })
+    }
+}
+
+/// The side of `voxel` facing the unit direction `normal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Face {
+    pub voxel: Voxel,
+    pub normal: Voxel,
+}
+
+impl Face {
+    /// The cube on the other side of the face.
+    pub fn neighbour(&self) -> Voxel {
+        let ((x, y, z), (dx, dy, dz)) = (self.voxel, self.normal);
+        (x + dx, y + dy, z + dz)
+    }
+
+    /// Corners counter-clockwise when seen from the side the normal points to.
+    pub fn corners(&self) -> [Voxel; 4] {
+        let (x, y, z) = self.voxel;
+        let corner = [x, y, z];
+        let normal = [self.normal.0, self.normal.1, self.normal.2];
+        let axis = normal.iter().position(|&n| n != 0).unwrap();
+        // the two other axes, in the order whose cross product points along `axis`
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        let mut base = corner;
+        if normal[axis] > 0 {
+            base[axis] += 1;
+        }
+        let offset = |du: i32, dv: i32| {
+            let mut point = base;
+            point[u] += du;
+            point[v] += dv;
+            (point[0], point[1], point[2])
+        };
+        let corners = [offset(0, 0), offset(1, 0), offset(1, 1), offset(0, 1)];
+        if normal[axis] > 0 {
+            corners
+        } else {
+            let [a, b, c, d] = corners;
+            [a, d, c, b]
+        }
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Voxels;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let cubes = input
+            .lines()
+            .map(|line| parse_line(input, line))
+            .collect::<Result<Vec<Voxel>>>()?;
+        if cubes.is_empty() {
+            return Err(Error::Invalid("there are no cubes in the droplet".to_string()));
+        }
+        let backend = Backend::suited_to(&cubes);
+        Ok(Voxels::new(cubes, backend))
+    }
+
+    fn part1(voxels: &Self::Input) -> Result<usize> {
+        Ok(voxels.surface_area())
+    }
+
+    fn part2(voxels: &Self::Input) -> Result<usize> {
+        Ok(voxels.exterior_surface_area())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn two_cubes() {
+        let voxels = Day18::parse("1,1,1\n2,1,1\n").unwrap();
+        assert_eq!(Day18::part1(&voxels).unwrap(), 10);
+    }
+
+    #[test]
+    fn example() {
+        let voxels = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part1(&voxels).unwrap(), 64);
+        assert_eq!(Day18::part2(&voxels).unwrap(), 58);
+    }
+
+    // a 4x4x4 cube with a hollow 2x2x1 middle
+    fn hollow_cube() -> Voxels {
+        iproduct!(0..4, 0..4, 0..4).filter(|&(x, y, z)| !((1..3).contains(&x) && (1..3).contains(&y) && z == 1)).collect()
+    }
+
+    #[test]
+    fn cavity_of_several_cubes() {
+        // only the outer faces count
+        let voxels = hollow_cube();
+        assert_eq!(Day18::part1(&voxels).unwrap(), 6 * 16 + 2 * 4 + 4 * 2);
+        assert_eq!(Day18::part2(&voxels).unwrap(), 6 * 16);
+    }
+
+    #[test]
+    fn volume_components_and_cavities() {
+        let voxels = hollow_cube();
+        assert_eq!(voxels.volume(), 64 - 4);
+        assert_eq!(voxels.bounds(), Some(((0, 0, 0), (3, 3, 3))));
+        assert_eq!(voxels.components().len(), 1);
+        let cavities = voxels.cavities();
+        assert_eq!(cavities.len(), 1);
+        assert_eq!(cavities[0].volume(), 4);
+        assert!(cavities[0].contains((2, 2, 1)));
+
+        let example = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(example.cavities().iter().map(Voxels::volume).collect::<Vec<_>>(), [1]);
+        let components = example.components();
+        // the cubes around the cavity only touch each other along edges
+        assert_eq!(components.iter().map(Voxels::volume).collect::<Vec<_>>(), [8, 1, 1, 1, 1, 1]);
+        assert!(components[0].contains((2, 2, 4)));
+        assert_eq!(Voxels::default().bounds(), None);
+    }
+
+    #[test]
+    fn meshes_of_one_cube() {
+        let cube = Voxels::from_iter([(0, 0, 0)]);
+        let stl = cube.to_stl("cube");
+        assert!(stl.starts_with("solid cube\n  facet normal -1 0 0\n    outer loop\n      vertex 0 0 0\n"));
+        assert!(stl.ends_with("endsolid cube\n"));
+        assert_eq!(stl.matches("facet normal").count(), 12);
+
+        let obj = cube.to_obj();
+        assert_eq!(obj.lines().filter(|line| line.starts_with("v ")).count(), 8);
+        assert_eq!(obj.lines().filter(|line| line.starts_with("f ")).count(), 6);
+        // the top face, counter-clockwise seen from above
+        assert_eq!(Face { voxel: (0, 0, 0), normal: (0, 0, 1) }.corners(), [(0, 0, 1), (1, 0, 1), (1, 1, 1), (0, 1, 1)]);
+        assert_eq!(Face { voxel: (0, 0, 0), normal: (0, 0, -1) }.corners(), [(0, 0, 0), (0, 1, 0), (1, 1, 0), (1, 0, 0)]);
+    }
+
+    #[test]
+    fn mesh_leaves_out_cavities() {
+        let voxels = hollow_cube();
+        assert_eq!(voxels.exterior_faces().len(), 6 * 16);
+        // every vertex of the outer surface of a 4x4x4 cube
+        let obj = voxels.to_obj();
+        assert_eq!(obj.lines().filter(|line| line.starts_with("v ")).count(), 5 * 5 * 5 - 3 * 3 * 3);
+    }
+
+    #[test]
+    fn backends_agree() {
+        let example = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(example.backend(), Backend::Dense);
+        // shifted below zero so the dense box needs its offset
+        let shifted = example.iter().map(|(x, y, z)| (x - 10, y - 3, -z)).collect::<Vec<_>>();
+        // about half of a 10x10x10 box, full of pockets and winding tunnels out
+        let mut seed = 7u32;
+        let blob = iproduct!(0..10, 0..10, 0..10).filter(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            seed >> 16 & 1 == 1
+        });
+        for voxels in [hollow_cube(), Voxels::from_iter(shifted), Voxels::from_iter(blob)] {
+            let dense = Voxels::new(voxels.iter(), Backend::Dense);
+            assert_eq!(dense, voxels);
+            assert_eq!(dense.volume(), voxels.volume());
+            assert_eq!(dense.bounds(), voxels.bounds());
+            assert_eq!(dense.surface_area(), voxels.surface_area());
+            assert_eq!(dense.exterior_surface_area(), voxels.exterior_surface_area());
+            assert_eq!(dense.cavities(), voxels.cavities());
+            assert_eq!(dense.components(), voxels.components());
+            assert_eq!(dense.to_obj(), voxels.to_obj());
+            assert!(dense.iter().tuple_windows().all(|(a, b)| a < b));
+        }
+        assert_eq!(Backend::suited_to(&[(0, 0, 0), (1000, 1000, 1000)]), Backend::Sparse);
+        assert!(Voxels::new([], Backend::Dense).is_empty());
+    }
+
+    #[test]
+    fn cubes_far_apart() {
+        let voxels = Day18::parse("0,0,0\n400,400,400\n").unwrap();
+        assert_eq!(voxels.backend(), Backend::Sparse);
+        assert_eq!(Day18::part1(&voxels).unwrap(), 12);
+        assert_eq!(Day18::part2(&voxels).unwrap(), 12);
+        assert!(voxels.cavities().is_empty());
+        assert_eq!(voxels.exterior_faces().len(), 12);
+
+        // a hollow cube far from a lone cube keeps its cavity
+        let far = hollow_cube().iter().chain([(-1000, 1000, 0)]).collect::<Vec<_>>();
+        assert_eq!(Backend::suited_to(&far), Backend::Sparse);
+        let voxels = Voxels::new(far, Backend::Sparse);
+        assert_eq!(voxels.exterior_surface_area(), 6 * 16 + 6);
+        assert_eq!(voxels.cavities().iter().map(Voxels::volume).collect::<Vec<_>>(), [4]);
+    }
+
+    #[test]
+    fn large_dense_droplet() {
+        // a solid cube of 216000 cubes
+        let voxels = Voxels::new(iproduct!(0..60, 0..60, 0..60), Backend::Dense);
+        assert_eq!(voxels.volume(), 216_000);
+        assert_eq!(voxels.surface_area(), 6 * 60 * 60);
+        assert_eq!(voxels.exterior_surface_area(), 6 * 60 * 60);
+    }
+
+    #[test]
+    fn reports_missing_coordinates() {
+        let err = Day18::parse("1,1,1\n2,1\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: expected `x,y,z`, found 2 values");
+    }
+}
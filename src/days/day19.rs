@@ -0,0 +1,536 @@
+use std::str::FromStr;
+
+use good_lp::{variable, Expression, ProblemVariables, SolverModel, Variable};
+use itertools::Itertools;
+use minilp::{ComparisonOp, LinearExpr};
+use rayon::prelude::*;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub struct ObsidianRobotCost {
+    pub ore: u16,
+    pub clay: u16,
+}
+
+
+impl ObsidianRobotCost {
+    fn new(ore: u16, clay: u16) -> Self {
+        Self { ore, clay }
+    }
+}
+
+
+#[derive(Debug)]
+pub struct GeodeRobotCost {
+    pub ore: u16,
+    pub obsidian: u16,
+}
+
+impl GeodeRobotCost {
+    fn new(ore: u16, obsidian: u16) -> Self {
+        Self { ore, obsidian }
+    }
+}
+
+
+#[derive(Debug)]
+pub struct Blueprint {
+    pub id: u16,
+    pub ore_robot_cost: u16,
+    pub clay_robot_cost: u16,
+    pub obsidian_robot_cost: ObsidianRobotCost,
+    pub geode_robot_cost: GeodeRobotCost
+}
+
+impl FromStr for Blueprint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_blueprint(s, s)
+    }
+}
+
+// `s` may span several lines; only the seven numbers in it matter
+fn parse_blueprint(input: &str, s: &str) -> Result<Blueprint> {
+    let numbers = parse::numbers::<u16>(s).collect::<Vec<u16>>();
+    let [id, ore_robot_cost, clay_robot_cost, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] = numbers[..] else {
+        return Err(Error::at(input, s, format!("expected a blueprint with 7 numbers, found {}", numbers.len())));
+    };
+
+    Ok(
+        Blueprint {
+            id,
+            ore_robot_cost,
+            clay_robot_cost,
+            obsidian_robot_cost: ObsidianRobotCost::new(obsidian_ore, obsidian_clay),
+            geode_robot_cost: GeodeRobotCost::new(geode_ore, geode_obsidian)
+        }
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Robot {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+/// Amounts of ore, clay and obsidian.
+type Minerals = [u16; 3];
+
+impl Blueprint {
+    fn cost(&self, robot: Robot) -> Minerals {
+        match robot {
+            Robot::Ore => [self.ore_robot_cost, 0, 0],
+            Robot::Clay => [self.clay_robot_cost, 0, 0],
+            Robot::Obsidian => [self.obsidian_robot_cost.ore, self.obsidian_robot_cost.clay, 0],
+            Robot::Geode => [self.geode_robot_cost.ore, 0, self.geode_robot_cost.obsidian],
+        }
+    }
+
+    // only one robot is built per minute, so mining more of a mineral per minute than the
+    // dearest robot needs of it is wasted
+    fn max_spend(&self) -> Minerals {
+        [
+            self.ore_robot_cost.max(self.clay_robot_cost).max(self.obsidian_robot_cost.ore).max(self.geode_robot_cost.ore),
+            self.obsidian_robot_cost.clay,
+            self.geode_robot_cost.obsidian,
+        ]
+    }
+}
+
+#[derive(Default, Clone, Copy, Hash, PartialEq, Eq)]
+struct State {
+    ore: u16,
+    clay: u16,
+    obsidian: u16,
+    geode: u16,
+    ore_robots: u16,
+    clay_robots: u16,
+    obsidian_robots: u16,
+    geode_robots: u16,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            ore_robots: 1,
+            ..Default::default()
+        }
+    }
+
+    fn collect_minerals(self, minutes: u16) -> State {
+        State {
+            ore: self.ore + self.ore_robots * minutes,
+            clay: self.clay + self.clay_robots * minutes,
+            obsidian: self.obsidian + self.obsidian_robots * minutes,
+            geode: self.geode + self.geode_robots * minutes,
+            ..self
+        }
+    }
+
+    fn robots(&self, robot: Robot) -> u16 {
+        match robot {
+            Robot::Ore => self.ore_robots,
+            Robot::Clay => self.clay_robots,
+            Robot::Obsidian => self.obsidian_robots,
+            Robot::Geode => self.geode_robots,
+        }
+    }
+
+    // minutes of collecting before `cost` is affordable, `None` if no robot mines a missing mineral
+    fn minutes_to_afford(&self, cost: Minerals) -> Option<u16> {
+        let stock = [(self.ore, self.ore_robots), (self.clay, self.clay_robots), (self.obsidian, self.obsidian_robots)];
+        stock.iter().zip(cost).try_fold(0, |minutes, (&(amount, robots), needed)| {
+            if amount >= needed {
+                Some(minutes)
+            } else if robots == 0 {
+                None
+            } else {
+                Some(minutes.max((needed - amount).div_ceil(robots)))
+            }
+        })
+    }
+
+    // waits until `robot` is affordable, then spends a minute building it while collecting
+    fn build(self, robot: Robot, blueprint: &Blueprint, wait: u16) -> State {
+        let [ore, clay, obsidian] = blueprint.cost(robot);
+        let mut state = self.collect_minerals(wait + 1);
+        state.ore -= ore;
+        state.clay -= clay;
+        state.obsidian -= obsidian;
+        match robot {
+            Robot::Ore => state.ore_robots += 1,
+            Robot::Clay => state.clay_robots += 1,
+            Robot::Obsidian => state.obsidian_robots += 1,
+            Robot::Geode => state.geode_robots += 1,
+        }
+        state
+    }
+
+    // geodes opened if ore and clay were free and a new obsidian robot and, when there is
+    // obsidian for one, a new geode robot came every minute; never fewer than really possible
+    fn upper_bound(&self, blueprint: &Blueprint, time_left: u16) -> u16 {
+        let (mut obsidian, mut obsidian_robots) = (self.obsidian, self.obsidian_robots);
+        let (mut geode, mut geode_robots) = (self.geode, self.geode_robots);
+        for _ in 0..time_left {
+            let build_geode_robot = obsidian >= blueprint.geode_robot_cost.obsidian;
+            obsidian += obsidian_robots;
+            geode += geode_robots;
+            obsidian_robots += 1;
+            if build_geode_robot {
+                obsidian -= blueprint.geode_robot_cost.obsidian;
+                geode_robots += 1;
+            }
+        }
+        geode
+    }
+}
+
+/// Searches the tree of build orders depth first, branching on which robot to save up for
+/// next, and returns the end state with the most geodes.
+fn create_state_tree(blueprint: &Blueprint, time: u16) -> State {
+    let mut best = State::new().collect_minerals(time);
+    explore(blueprint, &blueprint.max_spend(), State::new(), time, &mut best);
+    best
+}
+
+fn explore(blueprint: &Blueprint, max_spend: &Minerals, state: State, time_left: u16, best: &mut State) {
+    // building nothing more, the geode robots keep working
+    let idle = state.collect_minerals(time_left);
+    if idle.geode > best.geode {
+        *best = idle;
+    }
+    if state.upper_bound(blueprint, time_left) <= best.geode {
+        return;
+    }
+    for (robot, max_robots) in [
+        (Robot::Geode, u16::MAX),
+        (Robot::Obsidian, max_spend[2]),
+        (Robot::Clay, max_spend[1]),
+        (Robot::Ore, max_spend[0]),
+    ] {
+        if state.robots(robot) >= max_robots {
+            continue;
+        }
+        let Some(wait) = state.minutes_to_afford(blueprint.cost(robot)) else {
+            continue;
+        };
+        // a robot finished in the last minute has no time left to mine
+        if wait + 1 >= time_left {
+            continue;
+        }
+        let next = state.build(robot, blueprint, wait);
+        explore(blueprint, max_spend, next, time_left - wait - 1, best);
+    }
+}
+
+/// How to find the most geodes a blueprint can open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Method {
+    /// Depth-first search over build orders.
+    #[default]
+    Search,
+    /// Integer linear program over which robot is built each minute.
+    Ilp,
+}
+
+impl Method {
+    pub fn max_geodes(self, blueprint: &Blueprint, time: u16) -> u16 {
+        match self {
+            Method::Search => create_state_tree(blueprint, time).geode,
+            Method::Ilp => ilp_max_geodes(blueprint, time),
+        }
+    }
+}
+
+const ROBOTS: [Robot; 4] = [Robot::Ore, Robot::Clay, Robot::Obsidian, Robot::Geode];
+
+// variables of the linear program: whether each robot is built in each minute, anywhere from 0
+// to 1 in the relaxation and either once solved in integers
+struct Schedule {
+    time: u16,
+    build: Vec<[Variable; 4]>,
+}
+
+impl Schedule {
+    // ore, clay or obsidian mined by the end of minute `t`, counting from 0, by the robot that
+    // starts out and those built before
+    fn mined(&self, mineral: usize, t: usize) -> Expression {
+        let initial = if mineral == 0 { t as f64 + 1.0 } else { 0.0 };
+        let built = self.build[..t].iter().enumerate().map(|(u, build)| (t - u) as f64 * build[mineral]);
+        initial + built.sum::<Expression>()
+    }
+
+    fn spent(&self, blueprint: &Blueprint, mineral: usize, t: usize) -> Expression {
+        self.build[..=t]
+            .iter()
+            .flat_map(|build| ROBOTS.iter().zip(build).map(|(&robot, &built)| f64::from(blueprint.cost(robot)[mineral]) * built))
+            .sum()
+    }
+
+    fn geodes(&self) -> Expression {
+        self.build.iter().enumerate().map(|(t, build)| f64::from(self.time - 1 - t as u16) * build[3]).sum()
+    }
+}
+
+// minutes in which building each robot can pay off: none before it could be afforded if every
+// robot affordable were built each minute for free, and none too late for what it mines to
+// end up in a geode
+fn build_windows(blueprint: &Blueprint, time: u16) -> [(usize, usize); 4] {
+    let mut earliest = [None; 4];
+    let mut state = State::new();
+    for t in 0..time as usize {
+        for (r, &robot) in ROBOTS.iter().enumerate() {
+            if earliest[r].is_none() && state.minutes_to_afford(blueprint.cost(robot)) == Some(0) {
+                earliest[r] = Some(t);
+            }
+        }
+        state = state.collect_minerals(1);
+        state.ore_robots += u16::from(earliest[0].is_some());
+        state.clay_robots += u16::from(earliest[1].is_some());
+        state.obsidian_robots += u16::from(earliest[2].is_some());
+    }
+    // minutes from building a robot until a geode robot paid for with what it mines opens a geode
+    let lead = [3, 5, 3, 1];
+    [0, 1, 2, 3].map(|r| {
+        let latest = (time as usize).saturating_sub(lead[r] + 1);
+        (earliest[r].unwrap_or(usize::MAX), latest)
+    })
+}
+
+// the linear relaxation of the whole program, solved once, with the variables minilp gave the
+// robots built each minute; `None` when no plan fits at all
+fn solve_relaxation(blueprint: &Blueprint, time: u16) -> Option<(minilp::Solution, Vec<[minilp::Variable; 4]>)> {
+    let windows = build_windows(blueprint, time);
+    let mut variables = ProblemVariables::new();
+    let build = (0..time as usize)
+        .map(|t| {
+            [0, 1, 2, 3].map(|r| {
+                let max = if (windows[r].0..=windows[r].1).contains(&t) { 1 } else { 0 };
+                variables.add(variable().min(0).max(max))
+            })
+        })
+        .collect::<Vec<_>>();
+    let schedule = Schedule { time, build };
+    let mut model = variables.maximise(schedule.geodes()).using(good_lp::minilp);
+    for (t, build) in schedule.build.iter().enumerate() {
+        // one robot at a time, paid for with minerals mined in earlier minutes
+        model.add_constraint(build.iter().sum::<Expression>().leq(1));
+        for mineral in 0..3 {
+            let mined = if t == 0 { Expression::from(0) } else { schedule.mined(mineral, t - 1) };
+            model.add_constraint(schedule.spent(blueprint, mineral, t).leq(mined));
+        }
+    }
+    let solution = model.solve().ok()?.into_inner();
+    // minilp numbers the variables in the order they were added, four to a minute
+    let variables = solution.iter().map(|(variable, _)| variable).collect::<Vec<_>>();
+    let build = variables.chunks(4).map(|robots| [robots[0], robots[1], robots[2], robots[3]]).collect();
+    Some((solution, build))
+}
+
+// branch and bound over the linear relaxation, as minilp does not solve integer programs. The
+// model is built once; each branch adds one constraint to a copy of its parent's solution, which
+// minilp re-solves from the parent's optimum instead of from scratch. Branches split on how many
+// robots are built by some minute, dearest robots first, rather than on single minutes, which
+// cuts off whole plans that start a robot too early; the most promising branch goes first, and
+// the deepest among equally promising ones
+fn ilp_max_geodes(blueprint: &Blueprint, time: u16) -> u16 {
+    const EPSILON: f64 = 1e-6;
+    // geodes are whole, so only the whole part of a bound matters
+    let whole = |bound: f64| (bound + EPSILON).floor() as u16;
+    let Some((root, build)) = solve_relaxation(blueprint, time) else {
+        return 0;
+    };
+    // robots of kind `robot` built by the end of `minute`
+    let built = |robot: usize, minute: usize| build[..=minute].iter().map(move |robots| (robots[robot], 1.0));
+
+    let mut best = 0;
+    let mut pending = vec![(whole(root.objective()), 0, root)];
+    while let Some(i) = pending.iter().position_max_by_key(|&(bound, depth, _)| (*bound, *depth)) {
+        let (bound, depth, solution) = pending.swap_remove(i);
+        if bound <= best {
+            break;
+        }
+        let fractional = [3, 2, 1, 0]
+            .into_iter()
+            .flat_map(|r| (0..build.len()).map(move |t| (t, r)))
+            .map(|(t, r)| (t, r, built(r, t).map(|(variable, _)| solution[variable]).sum::<f64>()))
+            .find(|&(_, _, count)| (count - count.round()).abs() > EPSILON);
+        let Some((minute, robot, count)) = fractional else {
+            best = bound;
+            continue;
+        };
+        for (op, count) in [(ComparisonOp::Le, count.floor()), (ComparisonOp::Ge, count.ceil())] {
+            // infeasible branches are dropped
+            if let Ok(branch) = solution.clone().add_constraint(built(robot, minute).collect::<LinearExpr>(), op, count) {
+                if whole(branch.objective()) > best {
+                    pending.push((whole(branch.objective()), depth + 1, branch));
+                }
+            }
+        }
+    }
+    best
+}
+
+/// The most geodes each blueprint opens in `time` minutes, by the search and then by the
+/// integer program, which should always agree.
+pub fn cross_check(blueprints: &[Blueprint], time: u16) -> Vec<(u16, u16, u16)> {
+    blueprints
+        .par_iter()
+        .map(|blueprint| {
+            let search = Method::Search.max_geodes(blueprint, time);
+            (blueprint.id, search, Method::Ilp.max_geodes(blueprint, time))
+        })
+        .collect()
+}
+
+/// Sum of the quality levels of all blueprints.
+pub fn quality_level_sum(blueprints: &[Blueprint], method: Method) -> u32 {
+    blueprints
+        .par_iter()
+        .map(|blueprint| u32::from(blueprint.id) * u32::from(method.max_geodes(blueprint, 24)))
+        .sum()
+}
+
+/// Product of the most geodes of the first three blueprints in 32 minutes.
+pub fn first_three_product(blueprints: &[Blueprint], method: Method) -> u32 {
+    // the elephants ate all the others
+    blueprints[..blueprints.len().min(3)]
+        .par_iter()
+        .map(|blueprint| u32::from(method.max_geodes(blueprint, 32)))
+        .product()
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = Vec<Blueprint>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let input = input.trim_end();
+        if !input.starts_with("Blueprint") {
+            return Err(Error::at(input, input, "expected `Blueprint <id>:`"));
+        }
+        // blueprints are either one per line or spread over blank-line separated blocks
+        let starts = input.match_indices("Blueprint").map(|(i, _)| i).collect::<Vec<usize>>();
+        starts
+            .iter()
+            .zip(starts.iter().skip(1).chain([&input.len()]))
+            .map(|(&start, &end)| parse_blueprint(input, &input[start..end]))
+            .collect::<Result<Vec<Blueprint>>>()
+    }
+
+    fn part1(blueprints: &Self::Input) -> Result<u32> {
+        Ok(quality_level_sum(blueprints, Method::Search))
+    }
+
+    fn part2(blueprints: &Self::Input) -> Result<u32> {
+        Ok(first_three_product(blueprints, Method::Search))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn parses_one_blueprint_per_line() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(blueprints.len(), 2);
+        let blueprint = &blueprints[1];
+        assert_eq!((blueprint.id, blueprint.ore_robot_cost, blueprint.clay_robot_cost), (2, 2, 3));
+        assert_eq!((blueprint.obsidian_robot_cost.ore, blueprint.obsidian_robot_cost.clay), (3, 8));
+        assert_eq!((blueprint.geode_robot_cost.ore, blueprint.geode_robot_cost.obsidian), (3, 12));
+    }
+
+    #[test]
+    fn parses_blueprints_spread_over_lines() {
+        let spread = EXAMPLE.replace(": ", ":\n  ").replace(". ", ".\n  ").replace("\nBlueprint", "\n\nBlueprint");
+        let blueprints = Day19::parse(&spread).unwrap();
+        assert_eq!(blueprints.len(), 2);
+        assert_eq!(blueprints[0].geode_robot_cost.obsidian, 7);
+    }
+
+    #[test]
+    fn example() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Method::Search.max_geodes(&blueprints[0], 24), 9);
+        assert_eq!(Method::Search.max_geodes(&blueprints[1], 24), 12);
+        assert_eq!(Day19::part1(&blueprints).unwrap(), 33);
+        assert_eq!(Method::Search.max_geodes(&blueprints[0], 32), 56);
+        assert_eq!(Method::Search.max_geodes(&blueprints[1], 32), 62);
+        assert_eq!(Day19::part2(&blueprints).unwrap(), 56 * 62);
+    }
+
+    #[test]
+    fn part2_multiplies_the_first_three_blueprints() {
+        // a third blueprint counts, a fourth does not
+        let lines = EXAMPLE.lines().collect::<Vec<_>>();
+        let more = format!(
+            "{EXAMPLE}{}\n{}\n",
+            lines[1].replace("Blueprint 2", "Blueprint 3"),
+            lines[0].replace("Blueprint 1", "Blueprint 4"),
+        );
+        let blueprints = Day19::parse(&more).unwrap();
+        assert_eq!(blueprints.len(), 4);
+        assert_eq!(Day19::part2(&blueprints).unwrap(), 56 * 62 * 62);
+    }
+
+    #[test]
+    fn caps_and_bounds_follow_the_blueprint() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(blueprints[0].max_spend(), [4, 14, 7]);
+        assert_eq!(blueprints[1].max_spend(), [3, 8, 12]);
+        for blueprint in &blueprints {
+            let best = Method::Search.max_geodes(blueprint, 24);
+            assert!(State::new().upper_bound(blueprint, 24) >= best);
+        }
+        // the only ore robot mines two ore in two minutes, and nothing mines clay yet
+        assert_eq!(State::new().minutes_to_afford([2, 0, 0]), Some(2));
+        assert_eq!(State::new().minutes_to_afford([2, 1, 0]), None);
+    }
+
+    #[test]
+    fn cheap_blueprints_beyond_the_old_caps() {
+        // robots cost a single ore and the obsidian and geode robots are cheap, so the best
+        // plan piles up far more of every mineral than caps tuned to one input allowed
+        let blueprint: Blueprint = "Blueprint 1: Each ore robot costs 1 ore. Each clay robot costs 1 ore. \
+            Each obsidian robot costs 1 ore and 1 clay. Each geode robot costs 1 ore and 1 obsidian."
+            .parse()
+            .unwrap();
+        assert_eq!(blueprint.max_spend(), [1, 1, 1]);
+        // clay, obsidian and geode robots in minutes 2, 4 and 6 at the earliest, then a new
+        // geode robot every minute: 18 + 17 + ... + 1
+        assert_eq!(Method::Search.max_geodes(&blueprint, 24), 171);
+    }
+
+    #[test]
+    fn integer_program_agrees_with_the_search() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+        for time in [21, 24] {
+            for (id, search, ilp) in cross_check(&blueprints, time) {
+                assert_eq!(search, ilp, "blueprint {id} in {time} minutes");
+            }
+        }
+    }
+
+    #[test]
+    fn reports_incomplete_blueprints() {
+        let err = Day19::parse("Blueprint 1: Each ore robot costs 4 ore.\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: expected a blueprint with 7 numbers, found 2");
+    }
+}
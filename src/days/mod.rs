@@ -0,0 +1,55 @@
+//! Solutions for each day, and the registry mapping day numbers to them.
+
+use crate::solution::{Runner, Solver};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+
+static REGISTRY: [&dyn Runner; 19] = [
+    &Solver::<day01::Day01>::new(),
+    &Solver::<day02::Day02>::new(),
+    &Solver::<day03::Day03>::new(),
+    &Solver::<day04::Day04>::new(),
+    &Solver::<day05::Day05>::new(),
+    &Solver::<day06::Day06>::new(),
+    &Solver::<day07::Day07>::new(),
+    &Solver::<day08::Day08>::new(),
+    &Solver::<day09::Day09>::new(),
+    &Solver::<day10::Day10>::new(),
+    &Solver::<day11::Day11>::new(),
+    &Solver::<day12::Day12>::new(),
+    &Solver::<day13::Day13>::new(),
+    &Solver::<day14::Day14>::new(),
+    &Solver::<day15::Day15>::new(),
+    &Solver::<day16::Day16>::new(),
+    &Solver::<day17::Day17>::new(),
+    &Solver::<day18::Day18>::new(),
+    &Solver::<day19::Day19>::new(),
+];
+
+/// The solver registered for `day`, if there is one.
+pub fn get(day: u8) -> Option<&'static dyn Runner> {
+    REGISTRY.iter().copied().find(|runner| runner.day() == day)
+}
+
+/// Every registered solver, in calendar order.
+pub fn all() -> impl Iterator<Item = &'static dyn Runner> {
+    REGISTRY.iter().copied()
+}
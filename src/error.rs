@@ -0,0 +1,84 @@
+//! The error type shared by every parser and by the runner.
+
+use std::fmt;
+
+use crate::input::InputError;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Malformed puzzle input; line and column are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Well-formed input that does not describe a puzzle the solver can handle.
+    Invalid(String),
+    Input(InputError),
+}
+
+impl Error {
+    /// Parse error located at `fragment`, which must be a slice of `input`.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(offset <= input.len(), "fragment is not a slice of the input");
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        Error::Parse {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse { line, column, message } => {
+                write!(f, "line {line}, column {column}: {message}")
+            }
+            Error::Invalid(message) => write!(f, "{message}"),
+            Error::Input(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Input(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(err: InputError) -> Self {
+        Error::Input(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_of_fragment() {
+        let input = "first line\nsecond line";
+        let fragment = &input[18..];
+        match Error::at(input, fragment, "oops") {
+            Error::Parse { line, column, .. } => assert_eq!((line, column), (2, 8)),
+            err => panic!("unexpected error {err:?}"),
+        }
+    }
+
+    #[test]
+    fn position_at_end_of_input() {
+        let input = "abc\n";
+        let err = Error::at(input, &input[input.len()..], "unexpected end of input");
+        assert_eq!(err.to_string(), "line 2, column 1: unexpected end of input");
+    }
+}
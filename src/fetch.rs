@@ -0,0 +1,290 @@
+//! Downloading puzzle inputs and examples from the Advent of Code website into an inputs
+//! directory, so they can be loaded later with [`Source::Dir`](crate::input::Source::Dir).
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+use crate::input;
+
+pub const BASE_URL: &str = "https://adventofcode.com/2022";
+
+/// Environment variable holding the value of the `session` cookie of a logged-in browser.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Shortest time between two requests, to stay well within the site's fair-use policy.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), " (input downloader)");
+
+/// Name of the cached example for `day` inside an inputs directory, e.g. `day07.example`.
+pub fn example_file_name(day: u8) -> String {
+    format!("day{day:02}.example")
+}
+
+/// Session token from `$AOC_SESSION`, or else from the first line of [`session_file`].
+pub fn session_token() -> Result<String, FetchError> {
+    if let Some(token) = env::var(SESSION_VAR).ok().filter(|token| !token.trim().is_empty()) {
+        return Ok(token.trim().to_string());
+    }
+    let path = session_file().ok_or(FetchError::NoSession)?;
+    let token = fs::read_to_string(&path).map_err(|_| FetchError::NoSession)?;
+    token.lines().next().map(str::trim).filter(|token| !token.is_empty()).map(String::from).ok_or(FetchError::NoSession)
+}
+
+/// `$XDG_CONFIG_HOME/aoc/session`, falling back to `~/.config/aoc/session`.
+pub fn session_file() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config.join("aoc").join("session"))
+}
+
+/// HTTP client that waits at least `min_interval` between requests.
+pub struct Client {
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(session: String) -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            session,
+            min_interval: MIN_INTERVAL,
+            last_request: None,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).timeout(Duration::from_secs(30)).build(),
+        }
+    }
+
+    /// Sends requests to `base_url` instead of [`BASE_URL`], e.g. a local test server.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn input(&mut self, day: u8) -> Result<String, FetchError> {
+        self.get(&format!("/day/{day}/input"))
+    }
+
+    /// First example of the puzzle description, taken from its first `<pre><code>` block.
+    pub fn example(&mut self, day: u8) -> Result<String, FetchError> {
+        let page = self.get(&format!("/day/{day}"))?;
+        extract_example(&page).ok_or(FetchError::NoExample { day })
+    }
+
+    fn get(&mut self, path: &str) -> Result<String, FetchError> {
+        if let Some(wait) = self.last_request.and_then(|last| self.min_interval.checked_sub(last.elapsed())) {
+            thread::sleep(wait);
+        }
+        self.last_request = Some(Instant::now());
+
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(status, _) => FetchError::Status { url: url.clone(), status },
+                ureq::Error::Transport(transport) => FetchError::Transport { url: url.clone(), message: transport.to_string() },
+            })?;
+        response.into_string().map_err(|source| FetchError::Io { path: None, source })
+    }
+}
+
+fn extract_example(page: &str) -> Option<String> {
+    let start = page.find("<pre><code>")? + "<pre><code>".len();
+    let end = start + page[start..].find("</code></pre>")?;
+    // drop highlighting such as <em> and unescape the few entities the site uses
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    Some(text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&amp;", "&"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Download {
+    Fetched(PathBuf),
+    /// Already in the inputs directory, so no request was made.
+    Cached(PathBuf),
+}
+
+impl fmt::Display for Download {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Download::Fetched(path) => write!(f, "fetched {}", path.display()),
+            Download::Cached(path) => write!(f, "{} is already cached", path.display()),
+        }
+    }
+}
+
+/// Downloads the input and the example of `day` into `dir`, skipping files that exist.
+pub fn download(client: &mut Client, dir: &Path, day: u8) -> Result<[Download; 2], FetchError> {
+    fs::create_dir_all(dir).map_err(|source| FetchError::Io { path: Some(dir.to_path_buf()), source })?;
+    let input = cache(&dir.join(input::file_name(day)), || client.input(day))?;
+    let example = cache(&dir.join(example_file_name(day)), || client.example(day))?;
+    Ok([input, example])
+}
+
+fn cache(path: &Path, fetch: impl FnOnce() -> Result<String, FetchError>) -> Result<Download, FetchError> {
+    if path.exists() {
+        return Ok(Download::Cached(path.to_path_buf()));
+    }
+    let contents = fetch()?;
+    fs::write(path, contents).map_err(|source| FetchError::Io { path: Some(path.to_path_buf()), source })?;
+    Ok(Download::Fetched(path.to_path_buf()))
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession,
+    Status { url: String, status: u16 },
+    Transport { url: String, message: String },
+    NoExample { day: u8 },
+    // path is None when reading a response body
+    Io { path: Option<PathBuf>, source: io::Error },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession => {
+                write!(f, "no session token: set ${SESSION_VAR}")?;
+                match session_file() {
+                    Some(path) => write!(f, " or write it to {}", path.display()),
+                    None => Ok(()),
+                }
+            }
+            // the site answers 400 for an expired session and 404 for puzzles not yet released
+            FetchError::Status { url, status } => write!(f, "GET {url} failed with status {status}"),
+            FetchError::Transport { url, message } => write!(f, "GET {url} failed: {message}"),
+            FetchError::NoExample { day } => write!(f, "no example found in the description of day {day}"),
+            FetchError::Io { path: Some(path), source } => write!(f, "failed to write {}: {source}", path.display()),
+            FetchError::Io { path: None, source } => write!(f, "failed to read the response: {source}"),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    use super::*;
+
+    const PAGE: &str = "<article><p>For example:</p>\n<pre><code>1000\n<em>2000</em>\n&lt;3000&gt;\n</code></pre>\n\
+                        <pre><code>ignored</code></pre></article>";
+
+    // Stand-in for the website: answers each request with the next canned response and
+    // reports the request line and cookie of every request it served.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (requests, received) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut cookie = String::new();
+                for line in (&mut reader).lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.strip_prefix("Cookie: ") {
+                        cookie = value.to_string();
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.send((request_line.trim_end().to_string(), cookie)).unwrap();
+            }
+        });
+        (base_url, received)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn extracts_first_example() {
+        assert_eq!(extract_example(PAGE).unwrap(), "1000\n2000\n<3000>\n");
+        assert_eq!(extract_example("<p>no code</p>"), None);
+    }
+
+    #[test]
+    fn downloads_once_then_uses_the_cache() {
+        let (base_url, requests) = serve(vec![(200, "1\n2\n"), (200, PAGE)]);
+        let mut client = Client::new("abc123".to_string()).with_base_url(&base_url).with_min_interval(Duration::ZERO);
+        let dir = temp_dir("aoc_22_rust_fetch_cache");
+
+        let downloads = download(&mut client, &dir, 1).unwrap();
+        assert_eq!(downloads, [Download::Fetched(dir.join("day01.in")), Download::Fetched(dir.join("day01.example"))]);
+        assert_eq!(fs::read_to_string(dir.join("day01.in")).unwrap(), "1\n2\n");
+        assert_eq!(fs::read_to_string(dir.join("day01.example")).unwrap(), "1000\n2000\n<3000>\n");
+        assert_eq!(requests.recv().unwrap(), ("GET /day/1/input HTTP/1.1".to_string(), "session=abc123".to_string()));
+        assert_eq!(requests.recv().unwrap().0, "GET /day/1 HTTP/1.1");
+
+        // the stand-in has no responses left, so any request would fail
+        let downloads = download(&mut client, &dir, 1).unwrap();
+        assert!(matches!(downloads, [Download::Cached(_), Download::Cached(_)]));
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let (base_url, _requests) = serve(vec![(404, "not yet")]);
+        let mut client = Client::new("abc123".to_string()).with_base_url(&base_url);
+        let err = download(&mut client, &temp_dir("aoc_22_rust_fetch_404"), 25).unwrap_err();
+        assert!(matches!(err, FetchError::Status { status: 404, .. }));
+        assert_eq!(err.to_string(), format!("GET {base_url}/day/25/input failed with status 404"));
+    }
+
+    #[test]
+    fn waits_between_requests() {
+        let (base_url, _requests) = serve(vec![(200, "a"), (200, "b")]);
+        let interval = Duration::from_millis(200);
+        let mut client = Client::new("abc123".to_string()).with_base_url(&base_url).with_min_interval(interval);
+        let start = Instant::now();
+        assert_eq!(client.input(1).unwrap(), "a");
+        assert_eq!(client.input(2).unwrap(), "b");
+        assert!(start.elapsed() >= interval);
+    }
+}
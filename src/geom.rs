@@ -0,0 +1,81 @@
+//! Small geometry helpers: inclusive integer ranges and Manhattan distance.
+
+/// Inclusive integer interval `min..=max`.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, PartialEq, Eq, Hash)]
+pub struct Range {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Range {
+    pub fn new(min: i64, max: i64) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    /// True if `other` lies completely inside `self`.
+    pub fn contains_range(&self, other: &Self) -> bool {
+        self.min <= other.min && other.max <= self.max
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+
+    pub fn merge(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+/// Sorts the ranges and merges every overlapping run into a single range.
+pub fn merge_ranges(ranges: impl IntoIterator<Item = Range>) -> Vec<Range> {
+    let mut ranges: Vec<Range> = ranges.into_iter().collect();
+    ranges.sort();
+    ranges
+        .into_iter()
+        .fold(vec![], |mut acc: Vec<Range>, range| {
+            if let Some(last_range) = acc.last_mut() {
+                if last_range.overlaps(&range) {
+                    *last_range = last_range.merge(&range);
+                    return acc;
+                }
+            }
+            acc.push(range);
+            acc
+        })
+}
+
+pub fn manhattan(a: (i64, i64), b: (i64, i64)) -> u64 {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlap_is_symmetric() {
+        let (a, b) = (Range::new(2, 6), Range::new(4, 8));
+        assert!(a.overlaps(&b) && b.overlaps(&a));
+        assert!(!Range::new(2, 3).overlaps(&Range::new(4, 5)));
+        assert!(Range::new(2, 8).contains_range(&Range::new(3, 7)));
+        assert!(!Range::new(3, 7).contains_range(&Range::new(2, 8)));
+    }
+
+    #[test]
+    fn merges_unsorted_ranges() {
+        let ranges = [Range::new(12, 14), Range::new(-2, 2), Range::new(2, 5), Range::new(7, 13)];
+        assert_eq!(merge_ranges(ranges), vec![Range::new(-2, 5), Range::new(7, 14)]);
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        assert_eq!(manhattan((8, 7), (2, 10)), 9);
+    }
+}
@@ -0,0 +1,226 @@
+//! Owned rectangular grids of cells, addressed by `(row, col)` with `(0, 0)` at the top left.
+
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `rows` by `cols` cells, all set to `fill`.
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self { cells: vec![fill; rows * cols], rows, cols }
+    }
+
+    /// Grid from row-major cells; `None` unless `cells` holds exactly `rows * cols` of them.
+    pub fn from_cells(rows: usize, cols: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == rows * cols).then_some(Self { cells, rows, cols })
+    }
+
+    /// Parses a non-empty rectangle of text, one byte per cell. `cell` maps each byte to its
+    /// value, or to `None` when it is not a valid cell; `expected` describes valid cells in
+    /// error messages.
+    pub fn parse(text: &str, mut cell: impl FnMut(u8) -> Option<T>, expected: &str) -> Result<Self> {
+        let text = text.trim_end();
+        let cols = text.lines().next().unwrap_or_default().len();
+        if cols == 0 {
+            return Err(Error::Invalid("the grid is empty".to_string()));
+        }
+        let mut cells = Vec::with_capacity(text.len());
+        let mut rows = 0;
+        for line in text.lines() {
+            if line.len() != cols {
+                return Err(Error::at(text, line, format!("expected a row of {cols} cells, found {}", line.len())));
+            }
+            for (i, b) in line.bytes().enumerate() {
+                let value = cell(b).ok_or_else(|| Error::at(text, &line[i..], format!("expected {expected}")))?;
+                cells.push(value);
+            }
+            rows += 1;
+        }
+        Ok(Self { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        self.index_of(row, col).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        self.index_of(row, col).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.rows && col < self.cols).then_some(row * self.cols + col)
+    }
+
+    /// Appends a row at the bottom; `None` if it does not have `cols` cells.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) -> Option<()> {
+        let len = self.cells.len();
+        self.cells.extend(row);
+        if self.cells.len() - len != self.cols {
+            self.cells.truncate(len);
+            return None;
+        }
+        self.rows += 1;
+        Some(())
+    }
+
+    /// Every cell with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let cols = self.cols;
+        self.cells.iter().enumerate().map(move |(i, cell)| ((i / cols, i % cols), cell))
+    }
+
+    /// Position of the first cell matching `predicate`, in row-major order.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.cols, "column {col} is outside a grid of {} columns", self.cols);
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn row_iter(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    pub fn column_iter(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    /// Up, down, left and right neighbours of a cell that lie inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &[(-1, 0), (1, 0), (0, -1), (0, 1)])
+    }
+
+    /// Like [`Grid::neighbours4`], plus the four diagonal neighbours.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)])
+    }
+
+    fn offsets(&self, (row, col): Pos, deltas: &'static [(isize, isize)]) -> impl Iterator<Item = Pos> {
+        let (rows, cols) = (self.rows, self.cols);
+        deltas.iter().filter_map(move |&(dr, dc)| {
+            let r = row.checked_add_signed(dr)?;
+            let c = col.checked_add_signed(dc)?;
+            (r < rows && c < cols).then_some((r, c))
+        })
+    }
+
+    /// Grid mirrored along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.column_iter().flatten().cloned().collect();
+        Self { cells, rows: self.cols, cols: self.rows }
+    }
+
+    /// Text with one character per cell and a newline after every row.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity(self.rows * (self.cols + 1));
+        for row in self.row_iter() {
+            text.extend(row.iter().map(&mut cell));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Pos) -> &T {
+        self.get((row, col))
+            .unwrap_or_else(|| panic!("({row}, {col}) is outside a {}x{} grid", self.rows, self.cols))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Pos) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut((row, col))
+            .unwrap_or_else(|| panic!("({row}, {col}) is outside a {rows}x{cols} grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(text: &str) -> Grid<u8> {
+        Grid::parse(text, |b| b.is_ascii_alphabetic().then_some(b), "a letter").unwrap()
+    }
+
+    #[test]
+    fn shape_with_and_without_trailing_newline() {
+        for text in ["abc\ndef", "abc\ndef\n"] {
+            let grid = letters(text);
+            assert_eq!((grid.rows(), grid.cols()), (2, 3));
+            assert_eq!(grid.get((1, 2)), Some(&b'f'));
+            assert_eq!(grid.get((1, 3)), None);
+            assert_eq!(grid.get((2, 0)), None);
+        }
+    }
+
+    #[test]
+    fn position_and_neighbours() {
+        let grid = letters("abc\ndeS\n");
+        assert_eq!(grid.position(|&b| b == b'S'), Some((1, 2)));
+        let mut neighbours = grid.neighbours4((0, 0)).collect::<Vec<_>>();
+        neighbours.sort();
+        assert_eq!(neighbours, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8((0, 1)).count(), 5);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn rows_columns_and_transpose() {
+        let mut grid = letters("abc\ndef\n");
+        assert_eq!(grid.row(1), b"def");
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+        let transposed = grid.transpose();
+        assert_eq!(transposed.render(|&b| b as char), "ad\nbe\ncf\n");
+        assert_eq!(transposed.transpose(), grid);
+
+        grid[(0, 0)] = b'x';
+        assert!(grid.push_row(*b"gh").is_none());
+        assert!(grid.push_row(*b"ghi").is_some());
+        assert_eq!(grid.render(|&b| b as char), "xbc\ndef\nghi\n");
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let err = letters_err("abc\nde\n");
+        assert_eq!(err.to_string(), "line 2, column 1: expected a row of 3 cells, found 2");
+        let err = letters_err("abc\nd1f\n");
+        assert_eq!(err.to_string(), "line 2, column 2: expected a letter");
+    }
+
+    fn letters_err(text: &str) -> Error {
+        Grid::parse(text, |b| b.is_ascii_alphabetic().then_some(b), "a letter").unwrap_err()
+    }
+}
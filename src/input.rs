@@ -0,0 +1,151 @@
+//! Loading puzzle inputs at runtime, from a file, a directory of `dayNN.in` files or stdin.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+use crate::solution::Solution;
+
+/// Directory used when no input is given, unless the `AOC_INPUTS` environment variable is set.
+pub const DEFAULT_DIR: &str = "inputs";
+
+/// Name of the input file for `day` inside an inputs directory, e.g. `day07.in`.
+pub fn file_name(day: u8) -> String {
+    format!("day{day:02}.in")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    /// Directory holding one `dayNN.in` file per day.
+    Dir(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// `-` stands for stdin, anything else is a file or directory path.
+    pub fn from_arg(arg: &str) -> Self {
+        let path = PathBuf::from(arg);
+        match arg {
+            "-" => Source::Stdin,
+            _ if path.is_dir() => Source::Dir(path),
+            _ => Source::File(path),
+        }
+    }
+
+    /// `$AOC_INPUTS` if it is set, otherwise [`DEFAULT_DIR`].
+    pub fn default_dir() -> Self {
+        let dir = env::var_os("AOC_INPUTS").unwrap_or_else(|| DEFAULT_DIR.into());
+        Source::Dir(PathBuf::from(dir))
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        match self {
+            Source::File(path) => read_file(day, path.clone()),
+            Source::Dir(dir) => read_file(day, dir.join(file_name(day))),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|source| InputError::Io { path: None, source })?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Parsed input of `S` from `arg`, the optional path or `-` a day's binary takes, falling
+/// back to [`Source::default_dir`]. Prints the error and exits if loading or parsing fails.
+pub fn load_or_exit<S: Solution>(arg: Option<String>) -> S::Input {
+    let source = arg.map_or_else(Source::default_dir, |arg| Source::from_arg(&arg));
+    or_exit(
+        source
+            .load(S::DAY)
+            .map_err(crate::error::Error::from)
+            .and_then(|raw_input| S::parse(&raw_input)),
+    )
+}
+
+/// The value in `result`, or prints the error and exits with status 1.
+pub fn or_exit<T, E: fmt::Display>(result: Result<T, E>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+        process::exit(1);
+    })
+}
+
+fn read_file(day: u8, path: PathBuf) -> Result<String, InputError> {
+    if !path.exists() {
+        return Err(InputError::Missing { day, path });
+    }
+    fs::read_to_string(&path).map_err(|source| InputError::Io { path: Some(path), source })
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) | Source::Dir(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { day: u8, path: PathBuf },
+    // path is None when reading from stdin
+    Io { path: Option<PathBuf>, source: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { day, path } => {
+                write!(f, "no input for day {day}: {} does not exist", path.display())
+            }
+            InputError::Io { path: Some(path), source } => {
+                write!(f, "failed to read {}: {source}", path.display())
+            }
+            InputError::Io { path: None, source } => write!(f, "failed to read stdin: {source}"),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_file_names() {
+        assert_eq!(file_name(3), "day03.in");
+        assert_eq!(file_name(19), "day19.in");
+    }
+
+    #[test]
+    fn missing_file_in_directory() {
+        let dir = env::temp_dir().join("aoc_22_rust_missing_inputs");
+        fs::create_dir_all(&dir).unwrap();
+        let err = Source::Dir(dir.clone()).load(25).unwrap_err();
+        assert!(matches!(&err, InputError::Missing { day: 25, path } if *path == dir.join("day25.in")));
+        assert!(err.to_string().starts_with("no input for day 25: "));
+    }
+
+    #[test]
+    fn reads_from_directory() {
+        let dir = env::temp_dir().join("aoc_22_rust_inputs");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day01.in"), "1000\n").unwrap();
+        assert_eq!(Source::from_arg(dir.to_str().unwrap()).load(1).unwrap(), "1000\n");
+    }
+}
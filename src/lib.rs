@@ -0,0 +1,13 @@
+//! Shared building blocks for the Advent of Code 2022 solutions, and the solutions themselves.
+
+pub mod cli;
+pub mod days;
+pub mod error;
+pub mod fetch;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod search;
+pub mod solution;
+pub mod timing;
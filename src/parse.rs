@@ -0,0 +1,83 @@
+//! Helpers for the line- and block-oriented puzzle input formats.
+//!
+//! Functions taking an `input` argument expect the slices they are given to come from it,
+//! so errors can point at the offending line and column.
+
+use std::str::FromStr;
+
+use nom::IResult;
+
+use crate::error::{Error, Result};
+
+/// Splits input into the blocks separated by blank lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    input.trim_end().split("\n\n")
+}
+
+/// Every integer in `s`, ignoring whatever text surrounds them.
+pub fn numbers<T: FromStr>(s: &str) -> impl Iterator<Item = T> + '_ {
+    s.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter_map(|token| token.parse().ok())
+}
+
+/// Parses `token`, reporting its position in `input` if it is not a valid number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T> {
+    token
+        .trim()
+        .parse()
+        .map_err(|_| Error::at(input, token, format!("invalid number `{}`", token.trim())))
+}
+
+/// Splits `s` around the first `delimiter`, failing if there is none.
+pub fn split_once<'a>(input: &str, s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str)> {
+    s.split_once(delimiter)
+        .ok_or_else(|| Error::at(input, s, format!("expected `{delimiter}` in `{s}`")))
+}
+
+/// Unwraps the result of a nom parser run over `input`, which must consume all but
+/// trailing whitespace. `expected` describes the format in error messages.
+pub fn finish<'a, T>(input: &'a str, result: IResult<&'a str, T>, expected: &str) -> Result<T> {
+    let (rest, value) = result.map_err(|err| match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => {
+            Error::at(input, err.input, format!("expected {expected}"))
+        }
+        nom::Err::Incomplete(_) => Error::at(input, &input[input.len()..], "unexpected end of input"),
+    })?;
+    let rest = rest.trim_start();
+    if !rest.is_empty() {
+        return Err(Error::at(input, rest, format!("expected {expected}")));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_in_text() {
+        let values = numbers::<i64>("Sensor at x=2, y=-18: move 10").collect::<Vec<_>>();
+        assert_eq!(values, vec![2, -18, 10]);
+    }
+
+    #[test]
+    fn blocks_ignore_trailing_newlines() {
+        assert_eq!(blocks("a\nb\n\nc\n\n").collect::<Vec<_>>(), vec!["a\nb", "c"]);
+    }
+
+    #[test]
+    fn errors_point_into_the_input() {
+        let input = "1-2\n3-x";
+        let (_, bad) = input.split_once("3-").unwrap();
+        let err = number::<u32>(input, bad).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: invalid number `x`");
+    }
+
+    #[test]
+    fn finish_rejects_trailing_input() {
+        let input = "12 apples";
+        let result = nom::character::complete::u32::<_, nom::error::Error<&str>>(input);
+        let err = finish(input, result, "a number").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 4: expected a number");
+    }
+}
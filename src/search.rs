@@ -0,0 +1,263 @@
+//! Generic graph searches.
+//!
+//! Nodes are any hashable values and edges come from a neighbour function, so the same
+//! searches work on grid positions, valve indices or whole puzzle states. Paths are rebuilt
+//! from parent pointers, so memory stays linear in the number of visited nodes.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Breadth-first search from `start` to the first node satisfying `is_goal`.
+///
+/// Returns the path including both endpoints.
+pub fn bfs<N, FN, I, FG>(start: N, neighbours: FN, is_goal: FG) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    multi_source_bfs([start], neighbours, is_goal)
+}
+
+/// Like [`bfs`], but searches from every start at once, so the path found starts at
+/// whichever start is closest to a goal.
+pub fn multi_source_bfs<N, FN, I, FG>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: FN,
+    mut is_goal: FG,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    let mut queue = VecDeque::new();
+    let mut seen = HashSet::new();
+    for start in starts {
+        if is_goal(&start) {
+            return Some(vec![start]);
+        }
+        if seen.insert(start.clone()) {
+            queue.push_back(start);
+        }
+    }
+    let mut parents: HashMap<N, N> = HashMap::new();
+    while let Some(node) = queue.pop_front() {
+        for neighbour in neighbours(&node) {
+            if !seen.insert(neighbour.clone()) {
+                continue;
+            }
+            parents.insert(neighbour.clone(), node.clone());
+            if is_goal(&neighbour) {
+                return Some(reconstruct_path(&parents, neighbour));
+            }
+            queue.push_back(neighbour);
+        }
+    }
+    None
+}
+
+/// Every node reachable from `start`, including `start` itself.
+pub fn reachable<N, FN, I>(start: N, mut neighbours: FN) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for neighbour in neighbours(&node) {
+            if seen.insert(neighbour.clone()) {
+                stack.push(neighbour);
+            }
+        }
+    }
+    seen
+}
+
+/// Cheapest path from `start` to a node satisfying `is_goal`, with its total cost.
+///
+/// `successors` yields each neighbour with the non-negative cost of the edge to it.
+pub fn dijkstra<N, C, FN, I, FG>(start: N, successors: FN, is_goal: FG) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], guided by a `heuristic` that must never overestimate the remaining cost
+/// to a goal; otherwise the path found may not be the cheapest.
+pub fn astar<N, C, FN, I, FH, FG>(start: N, successors: FN, heuristic: FH, is_goal: FG) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    let search = explore(start, successors, heuristic, is_goal);
+    let goal = search.goal?;
+    let cost = search.costs[&goal];
+    Some((reconstruct_path(&search.parents, goal), cost))
+}
+
+/// Cost of the cheapest path from `start` to every node reachable from it.
+pub fn dijkstra_all<N, C, FN, I>(start: N, successors: FN) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    explore(start, successors, |_| C::default(), |_| false).costs
+}
+
+/// Cost of the cheapest path between every pair of `nodes`, indexed by their positions in
+/// `nodes`; `None` when there is no path.
+pub fn all_pairs_shortest_paths<N, C, FN, I>(nodes: &[N], mut successors: FN) -> Vec<Vec<Option<C>>>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    nodes
+        .iter()
+        .map(|from| {
+            let costs = dijkstra_all(from.clone(), &mut successors);
+            nodes.iter().map(|to| costs.get(to).copied()).collect()
+        })
+        .collect()
+}
+
+struct Explored<N, C> {
+    goal: Option<N>,
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+fn explore<N, C, FN, I, FH, FG>(start: N, mut successors: FN, mut heuristic: FH, mut is_goal: FG) -> Explored<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut parents = HashMap::new();
+    // the heap refers to nodes by their index in `pushed`, so nodes need not be ordered
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut pushed = vec![start];
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        let node = &pushed[i];
+        if cost > costs[node] {
+            // a cheaper path to this node was already expanded
+            continue;
+        }
+        if is_goal(node) {
+            let goal = Some(node.clone());
+            return Explored { goal, costs, parents };
+        }
+        let node = node.clone();
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_some_and(|&known| known <= next_cost) {
+                continue;
+            }
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), node.clone());
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, pushed.len())));
+            pushed.push(next);
+        }
+    }
+    Explored { goal: None, costs, parents }
+}
+
+fn reconstruct_path<N: Eq + Hash + Clone>(parents: &HashMap<N, N>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // directed graph with a cheap detour: 0 -> 1 costs 10, 0 -> 2 -> 1 costs 3
+    fn weighted(node: &u8) -> Vec<(u8, u32)> {
+        match node {
+            0 => vec![(1, 10), (2, 1)],
+            2 => vec![(1, 2), (3, 7)],
+            1 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn shortest_path_on_a_line() {
+        let path = bfs(0, |&n| [n - 1, n + 1], |&n| n == 4).unwrap();
+        assert_eq!(path, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let path = bfs(0u8, |&n| (n < 10).then_some(n + 1), |&n| n == 20);
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn multi_source_starts_from_the_closest_start() {
+        let path = multi_source_bfs([0, 7, 20], |&n| [n - 1, n + 1], |&n| n == 10).unwrap();
+        assert_eq!(path, vec![7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn reachable_from_a_node() {
+        let nodes = reachable(0u8, |&n| (n < 5).then_some(n + 1));
+        assert_eq!(nodes, HashSet::from([0, 1, 2, 3, 4, 5]));
+        let unweighted = |n: &u8| weighted(n).into_iter().map(|(next, _)| next);
+        assert_eq!(reachable(2, unweighted), HashSet::from([2, 1, 3]));
+    }
+
+    #[test]
+    fn dijkstra_takes_the_cheap_detour() {
+        assert_eq!(dijkstra(0, weighted, |&n| n == 3), Some((vec![0, 2, 1, 3], 4)));
+        assert_eq!(dijkstra(3, weighted, |&n| n == 0), None);
+    }
+
+    #[test]
+    fn astar_with_manhattan_heuristic() {
+        let goal = (3i32, 2i32);
+        let (path, cost) = astar(
+            (0, 0),
+            |&(x, y)| [(x + 1, y), (x, y + 1), (x - 1, y)].map(|next| (next, 1)),
+            |&(x, y): &(i32, i32)| x.abs_diff(goal.0) + y.abs_diff(goal.1),
+            |&pos| pos == goal,
+        )
+        .unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!((path.len(), path[0], path[5]), (6, (0, 0), goal));
+    }
+
+    #[test]
+    fn all_pairs() {
+        let distances = all_pairs_shortest_paths(&[0, 1, 2, 3], weighted);
+        assert_eq!(distances[0], vec![Some(0), Some(3), Some(1), Some(4)]);
+        assert_eq!(distances[3], vec![None, None, None, Some(0)]);
+    }
+}
@@ -0,0 +1,117 @@
+//! The interface every day implements, plus a type-erased wrapper so days can be
+//! driven uniformly from the registry in [`crate::days`].
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+
+/// One day of the calendar: parse the puzzle input once, then solve both parts from it.
+///
+/// A part fails with [`crate::error::Error::Invalid`] when the parsed input has no answer for
+/// it, so input that only one part can solve still parses.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Output1>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    fn includes_one(self) -> bool {
+        matches!(self, Part::One | Part::Both)
+    }
+
+    fn includes_two(self) -> bool {
+        matches!(self, Part::Two | Part::Both)
+    }
+}
+
+/// Rendered answers of a run; parts that were not requested are `None`, parts that failed hold
+/// the error.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<Result<String, String>>,
+    pub part2: Option<Result<String, String>>,
+    pub timings: Timings,
+}
+
+/// Wall-clock time spent in each step of a run, excluding rendering the answers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+// rendered answer or error of a part that was run, and the time it took
+fn timed_part<T: Display>(run: bool, f: impl FnOnce() -> Result<T>) -> Option<(Result<String, String>, Duration)> {
+    run.then(|| {
+        let (answer, duration) = timed(f);
+        (answer.map(|answer| answer.to_string()).map_err(|err| err.to_string()), duration)
+    })
+}
+
+/// Object-safe view of a [`Solution`], with the answers rendered to text.
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+    fn run(&self, input: &str, part: Part) -> Result<Answers>;
+}
+
+/// Adapts a [`Solution`] implementation into a [`Runner`].
+pub struct Solver<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> Solver<S> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: Solution> Default for Solver<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Solution> Runner for Solver<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, input: &str, part: Part) -> Result<Answers> {
+        let (input, parse) = timed(|| S::parse(input));
+        let input = input?;
+        let (part1, part1_time) = timed_part(part.includes_one(), || S::part1(&input)).unzip();
+        let (part2, part2_time) = timed_part(part.includes_two(), || S::part2(&input)).unzip();
+        Ok(Answers {
+            part1,
+            part2,
+            timings: Timings { parse, part1: part1_time, part2: part2_time },
+        })
+    }
+}
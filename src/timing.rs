@@ -0,0 +1,148 @@
+//! Timing reports for runner results, as plain text, a Markdown table or JSON.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::solution::Timings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Markdown,
+    Json,
+}
+
+/// Timings of one day in a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Row {
+    pub day: u8,
+    pub timings: Timings,
+}
+
+/// Human readable duration with a unit suited to its size, e.g. `512 µs` or `1.20 s`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    match nanos {
+        0..=999 => format!("{nanos} ns"),
+        1_000..=999_999 => format!("{:.1} µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2} ms", nanos as f64 / 1e6),
+        _ => format!("{:.2} s", nanos as f64 / 1e9),
+    }
+}
+
+fn format_optional(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".to_string(), format_duration)
+}
+
+/// One line summarising the timings of a single day.
+pub fn text(timings: &Timings) -> String {
+    format!(
+        "parse {}, part 1 {}, part 2 {}, total {}",
+        format_duration(timings.parse),
+        format_optional(timings.part1),
+        format_optional(timings.part2),
+        format_duration(timings.total()),
+    )
+}
+
+/// Table with a row per day and a final row with the total of every column, `-` for parts
+/// no day ran.
+pub fn markdown(rows: &[Row]) -> String {
+    let mut table = String::from(
+        "| Day | Parse | Part 1 | Part 2 | Total |\n|----:|------:|-------:|-------:|------:|\n",
+    );
+    for Row { day, timings } in rows {
+        writeln!(
+            table,
+            "| {day:02} | {} | {} | {} | {} |",
+            format_duration(timings.parse),
+            format_optional(timings.part1),
+            format_optional(timings.part2),
+            format_duration(timings.total()),
+        )
+        .unwrap();
+    }
+    let sum = |f: fn(&Timings) -> Option<Duration>| {
+        rows.iter()
+            .filter_map(|row| f(&row.timings))
+            .reduce(|total, duration| total + duration)
+    };
+    writeln!(
+        table,
+        "| **Total** | {} | {} | {} | {} |",
+        format_optional(sum(|t| Some(t.parse))),
+        format_optional(sum(|t| t.part1)),
+        format_optional(sum(|t| t.part2)),
+        format_optional(sum(|t| Some(t.total()))),
+    )
+    .unwrap();
+    table
+}
+
+/// JSON array with an object per day; durations are in nanoseconds, `null` for parts not run.
+pub fn json(rows: &[Row]) -> String {
+    let nanos = |duration: Option<Duration>| {
+        duration.map_or_else(|| "null".to_string(), |d| d.as_nanos().to_string())
+    };
+    let objects = rows
+        .iter()
+        .map(|Row { day, timings }| {
+            format!(
+                "  {{\"day\": {day}, \"parse_ns\": {}, \"part1_ns\": {}, \"part2_ns\": {}, \"total_ns\": {}}}",
+                nanos(Some(timings.parse)),
+                nanos(timings.part1),
+                nanos(timings.part2),
+                nanos(Some(timings.total())),
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        let timings = Timings {
+            parse: Duration::from_micros(20),
+            part1: Some(Duration::from_millis(3)),
+            part2: None,
+        };
+        vec![Row { day: 4, timings }]
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_500)), "1.5 µs");
+        assert_eq!(format_duration(Duration::from_micros(2_345)), "2.35 ms");
+        assert_eq!(format_duration(Duration::from_millis(1_200)), "1.20 s");
+    }
+
+    #[test]
+    fn markdown_table() {
+        let table = markdown(&rows());
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines[2], "| 04 | 20.0 µs | 3.00 ms | - | 3.02 ms |");
+        assert_eq!(
+            lines[3],
+            "| **Total** | 20.0 µs | 3.00 ms | - | 3.02 ms |"
+        );
+
+        // a part counts towards the total of the days that ran it
+        let mut rows = rows();
+        let timings = Timings { parse: Duration::from_micros(30), part1: None, part2: Some(Duration::from_millis(5)) };
+        rows.push(Row { day: 5, timings });
+        let table = markdown(&rows);
+        assert_eq!(table.lines().last(), Some("| **Total** | 50.0 µs | 3.00 ms | 5.00 ms | 8.05 ms |"));
+    }
+
+    #[test]
+    fn json_array() {
+        assert_eq!(
+            json(&rows()),
+            "[\n  {\"day\": 4, \"parse_ns\": 20000, \"part1_ns\": 3000000, \"part2_ns\": null, \"total_ns\": 3020000}\n]\n"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Answers for the puzzle inputs checked into `inputs/`, so refactors can't silently change them.
+
+use std::path::Path;
+
+use aoc_22_rust::days;
+use aoc_22_rust::input::Source;
+use aoc_22_rust::solution::{Answers, Part};
+
+fn solve(day: u8, part: Part) -> Answers {
+    let source = Source::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"));
+    let input = source.load(day).unwrap();
+    days::get(day).unwrap().run(&input, part).unwrap()
+}
+
+fn assert_answers(day: u8, part1: &str, part2: &str) {
+    let answers = solve(day, Part::Both);
+    assert_eq!(answers.part1, Some(Ok(part1.to_string())), "day {day} part 1");
+    assert_eq!(answers.part2, Some(Ok(part2.to_string())), "day {day} part 2");
+}
+
+#[test]
+fn day01() {
+    assert_answers(1, "70698", "206643");
+}
+
+#[test]
+fn day02() {
+    assert_answers(2, "10624", "14060");
+}
+
+#[test]
+fn day03() {
+    assert_answers(3, "7848", "2616");
+}
+
+#[test]
+fn day04() {
+    assert_answers(4, "562", "924");
+}
+
+#[test]
+fn day05() {
+    assert_answers(5, "VJSFHWGFT", "LCTQFBVZV");
+}
+
+#[test]
+fn day06() {
+    assert_answers(6, "1987", "3059");
+}
+
+#[test]
+fn day07() {
+    assert_answers(7, "95437", "24933642");
+}
+
+#[test]
+fn day08() {
+    assert_answers(8, "1546", "519064");
+}
+
+#[test]
+fn day09() {
+    assert_answers(9, "6243", "2630");
+}
+
+#[test]
+fn day10() {
+    let screen = "\
+###..#....###...##..####.###...##..#....
+#..#.#....#..#.#..#.#....#..#.#..#.#....
+#..#.#....#..#.#..#.###..###..#....#....
+###..#....###..####.#....#..#.#....#....
+#....#....#....#..#.#....#..#.#..#.#....
+#....####.#....#..#.#....###...##..####.";
+    assert_answers(10, "12560", screen);
+}
+
+#[test]
+fn day11() {
+    assert_answers(11, "113232", "29703395016");
+}
+
+#[test]
+fn day12() {
+    assert_answers(12, "528", "522");
+}
+
+#[test]
+fn day13() {
+    assert_answers(13, "5843", "26289");
+}
+
+#[test]
+fn day14() {
+    assert_answers(14, "24", "93");
+}
+
+#[test]
+fn day15() {
+    assert_answers(15, "5525847", "13340867187704");
+}
+
+#[test]
+fn day16() {
+    assert_answers(16, "1651", "1707");
+}
+
+#[test]
+fn day17() {
+    assert_answers(17, "3068", "1514285714288");
+}
+
+#[test]
+fn day18() {
+    assert_answers(18, "3396", "2044");
+}
+
+#[test]
+fn day19() {
+    assert_answers(19, "33", "3472");
+}